@@ -15,30 +15,42 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use adventofcode_2024::common::day_input_filename;
-use adventofcode_2024::common::get_file_lines;
+use adventofcode_2024::common::ParseError;
 use adventofcode_2024::common::Solution;
 use adventofcode_2024::days;
+use adventofcode_2024::input::Input;
+use adventofcode_2024::solver::DaySolver;
+use adventofcode_2024::solver::Params;
 
 macro_rules! setup_benchmark {
-    ($($day_name:ident),*) => {
+    ($($day_name:ident),* $(,)?) => {
         $(
             pub fn $day_name(c: &mut criterion::Criterion) {
                 let day_name = stringify!($day_name);
                 let day_num: u8 = day_name[3..].parse().unwrap();
-                c.bench_function(&format!("Day {}", day_num), |bencher| {
-                    let input_lines = get_file_lines(&day_input_filename(day_num)).unwrap();
-                    bencher.iter(|| days::$day_name::solve(&input_lines));
+                let solver = days::get_solver(day_num).unwrap();
+                let input = Input::read(&day_input_filename(day_num)).unwrap();
+                let parsed = solver.parse(&input).unwrap();
+                let params = Params::defaults(solver.params());
+                c.bench_function(&format!("Day {} parse", day_num), |bencher| {
+                    bencher.iter(|| solver.parse(&input).map(|_| ()));
+                });
+                c.bench_function(&format!("Day {} A", day_num), |bencher| {
+                    bencher.iter(|| parsed.part_a(&params));
+                });
+                c.bench_function(&format!("Day {} B", day_num), |bencher| {
+                    bencher.iter(|| parsed.part_b(&params));
                 });
             }
         )*
 
         pub fn days_all(c: &mut criterion::Criterion) {
-            let solvers_and_inputs: Vec<(fn(&[String]) -> Solution, Vec<String>)> = days::all_numbers()
+            let solvers_and_inputs: Vec<(&'static dyn DaySolver, Input)> = days::all_numbers()
                 .into_iter()
                 .map(|day| {
                     (
                         days::get_solver(day).unwrap(),
-                        get_file_lines(&day_input_filename(day)).unwrap(),
+                        Input::read(&day_input_filename(day)).unwrap(),
                     )
                 })
                 .collect();
@@ -47,8 +59,23 @@ macro_rules! setup_benchmark {
                 bencher.iter(|| {
                     solvers_and_inputs
                         .iter()
-                        .map(|(solver, input)| solver(&input))
-                        .collect::<Vec<Solution>>()
+                        .map(|(solver, input)| solver.solve(&input))
+                        .collect::<Vec<Result<Solution, ParseError>>>()
+                })
+            });
+
+            c.bench_function("All days (parallel)", |bencher| {
+                bencher.iter(|| {
+                    std::thread::scope(|scope| {
+                        let handles: Vec<_> = solvers_and_inputs
+                            .iter()
+                            .map(|(solver, input)| scope.spawn(move || solver.solve(input)))
+                            .collect();
+                        handles
+                            .into_iter()
+                            .map(|handle| handle.join().unwrap())
+                            .collect::<Vec<Result<Solution, ParseError>>>()
+                    })
                 })
             });
         }
@@ -66,7 +93,4 @@ macro_rules! setup_benchmark {
     };
 }
 
-setup_benchmark!(
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
-);
+adventofcode_2024::with_days!(setup_benchmark);
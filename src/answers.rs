@@ -0,0 +1,219 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::common::ParseError;
+use crate::input::Input;
+use crate::solver::Part;
+
+/**
+ * Known correct answers, keyed by day and part. The registry is stored in
+ * `answers.toml` as one table per day:
+ *
+ * ```toml
+ * [day01]
+ * a = "2815556"
+ * b = "23927637"
+ * ```
+ *
+ * Only this subset of TOML is supported: `[dayNN]` table headers, `a` and `b`
+ * keys with basic string values, comments and blank lines.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+/**
+ * The result of comparing an answer against the registry.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let input = Input::new(source);
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (line_num, line) in input.numbered_lines() {
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = content.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "']'"))?;
+                let digits = name
+                    .strip_prefix("day")
+                    .ok_or_else(|| ParseError::at(line_num, line, name, "\"day<NN>\""))?;
+                day = Some(
+                    digits
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|d| (1..=25).contains(d))
+                        .ok_or_else(|| ParseError::at(line_num, line, digits, "day 1 - 25"))?,
+                );
+                continue;
+            }
+
+            let (key, value) = content
+                .split_once('=')
+                .ok_or_else(|| ParseError::at(line_num, line, content, "<part> = \"<answer>\""))?;
+            let key = key.trim();
+            let part: Part = match key {
+                "a" | "b" => key.parse().unwrap(),
+                _ => return Err(ParseError::at(line_num, line, key, "'a' or 'b'")),
+            };
+            let day = day.ok_or_else(|| ParseError::at(line_num, line, key, "\"[day<NN>]\""))?;
+            let value = parse_string(line_num, line, value.trim())?;
+            answers.insert((day, part), value);
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Answers file not found: {path:?}"))
+        })?;
+        Self::parse(&source).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        let mut days: Vec<u8> = self.answers.keys().map(|(day, _)| *day).collect();
+        days.dedup();
+        days.into_iter()
+    }
+
+    pub fn check(&self, day: u8, part: Part, actual: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+fn parse_string(line_num: usize, line: &str, value: &str) -> Result<String, ParseError> {
+    let mut chars = value.char_indices();
+    if chars.next().map(|(_, c)| c) != Some('"') {
+        return Err(ParseError::at(line_num, line, value, "'\"'"));
+    }
+
+    let mut result = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let rest = value[i + 1..].trim_start();
+                if rest.is_empty() || rest.starts_with('#') {
+                    return Ok(result);
+                } else {
+                    return Err(ParseError::at(line_num, line, rest, "end of line"));
+                }
+            }
+            '\\' => match chars.next() {
+                Some((_, '"')) => result.push('"'),
+                Some((_, '\\')) => result.push('\\'),
+                Some((j, _)) => {
+                    return Err(ParseError::at(
+                        line_num,
+                        line,
+                        &value[i..j + 1],
+                        "'\\\\\"' or '\\\\\\\\'",
+                    ))
+                }
+                None => break,
+            },
+            c => result.push(c),
+        }
+    }
+    Err(ParseError::end_of_line(line_num, line, "'\"'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+    use super::Verdict;
+    use crate::common::ParseError;
+    use crate::solver::Part;
+
+    #[test]
+    fn parses_tables_of_answers() {
+        let answers = Answers::parse(
+            r#"
+# Known answers
+[day01]
+a = "12"
+b = "34" # trailing comment
+
+[day25]
+a = "a,\"b\\"
+b = ""
+"#,
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, Part::A), Some("12"));
+        assert_eq!(answers.get(1, Part::B), Some("34"));
+        assert_eq!(answers.get(25, Part::A), Some("a,\"b\\"));
+        assert_eq!(answers.get(25, Part::B), Some(""));
+        assert_eq!(answers.get(2, Part::A), None);
+        assert_eq!(answers.days().collect::<Vec<_>>(), vec![1, 25]);
+    }
+
+    #[test]
+    fn check_compares_with_registry() {
+        let answers = Answers::parse("[day03]\na = \"7\"\n").unwrap();
+        assert_eq!(answers.check(3, Part::A, "7"), Verdict::Pass);
+        assert_eq!(
+            answers.check(3, Part::A, "8"),
+            Verdict::Fail {
+                expected: "7".to_string()
+            }
+        );
+        assert_eq!(answers.check(3, Part::B, "7"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn reports_answers_outside_tables() {
+        assert_eq!(
+            Answers::parse("a = \"1\"\n"),
+            Err(ParseError::new(1, 1, "\"[day<NN>]\"", "\"a\""))
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_strings() {
+        assert_eq!(
+            Answers::parse("[day01]\nb = \"1\n"),
+            Err(ParseError::new(2, 7, "'\"'", "end of line"))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+
+use crate::common::ParseError;
+use crate::days;
+use crate::input::Input;
+use crate::solver::DaySolver;
+use crate::solver::Example;
+use crate::solver::Param;
+use crate::solver::Part;
+
+/**
+ * The answers to both parts of a day's puzzle, as computed by [solve]. Not
+ * to be confused with the registry of known correct answers,
+ * [crate::answers::Answers].
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Answers {
+    pub a: String,
+    pub b: String,
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> &str {
+        match part {
+            Part::A => &self.a,
+            Part::B => &self.b,
+        }
+    }
+}
+
+/**
+ * Why a day could not be solved.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /** No solver is registered for the day. */
+    UnknownDay(u8),
+    /** The input is malformed. */
+    Parse(ParseError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownDay(day) => write!(f, "Unknown day: {day}"),
+            Self::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnknownDay(_) => None,
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+/**
+ * Solve both parts of `day` for `input`, with the default parameters.
+ *
+ * The input is normalized first, like inputs read by the runner. A solver
+ * that panics on an input it can't handle is not stopped from doing so.
+ */
+pub fn solve(day: u8, input: &str) -> Result<Answers, Error> {
+    DayInfo::get(day)
+        .ok_or(Error::UnknownDay(day))?
+        .solve(input)
+}
+
+/**
+ * A registered day and what it declares about its puzzle.
+ */
+#[derive(Clone, Copy)]
+pub struct DayInfo {
+    solver: &'static dyn DaySolver,
+}
+
+impl DayInfo {
+    /**
+     * The registered `day`, if any.
+     */
+    pub fn get(day: u8) -> Option<Self> {
+        days::get_solver(day).map(|solver| Self { solver })
+    }
+
+    pub fn day(&self) -> u8 {
+        self.solver.day()
+    }
+
+    /** The title of the puzzle, without the "Day N:" prefix. */
+    pub fn title(&self) -> &'static str {
+        self.solver.title()
+    }
+
+    /** The tunable parameters of the puzzle, with their defaults. */
+    pub fn params(&self) -> &'static [Param] {
+        self.solver.params()
+    }
+
+    /** The published examples, with their expected answers. */
+    pub fn examples(&self) -> &'static [Example] {
+        self.solver.examples()
+    }
+
+    /**
+     * Solve both parts for `input`, like [solve].
+     */
+    pub fn solve(&self, input: &str) -> Result<Answers, Error> {
+        let (a, b) = self.solver.solve(&Input::new(input))?;
+        Ok(Answers { a, b })
+    }
+}
+
+impl std::fmt::Debug for DayInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DayInfo")
+            .field("day", &self.day())
+            .field("title", &self.title())
+            .field("params", &self.params())
+            .field("examples", &self.examples())
+            .finish()
+    }
+}
+
+/**
+ * The registered days, in order.
+ */
+pub fn all_days() -> impl Iterator<Item = DayInfo> {
+    days::all_numbers().into_iter().filter_map(DayInfo::get)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::all_days;
+    use super::solve;
+    use super::DayInfo;
+    use super::Error;
+    use crate::common::ParseError;
+
+    #[test]
+    fn solve_returns_both_answers() {
+        let answers = solve(1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        assert_eq!((answers.a.as_str(), answers.b.as_str()), ("11", "31"));
+    }
+
+    #[test]
+    fn solve_reports_unknown_day_and_parse_errors() {
+        assert_eq!(solve(26, ""), Err(Error::UnknownDay(26)));
+        assert_eq!(
+            solve(1, "3 x\n"),
+            Err(Error::Parse(
+                ParseError::new(1, 3, "location ID", "\"x\"").in_day(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn all_days_have_titles_and_examples() {
+        let days: Vec<DayInfo> = all_days().collect();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[17].day(), 18);
+        assert_eq!(days[17].title(), "RAM Run");
+        assert!(days[17].params().iter().any(|param| param.name == "size"));
+        assert!(days.iter().all(|day| !day.title().is_empty()));
+        assert!(days.iter().all(|day| !day.examples().is_empty()));
+    }
+}
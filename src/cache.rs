@@ -0,0 +1,316 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use crate::client::write_atomically;
+use adventofcode_2024::common::parse_token;
+use adventofcode_2024::common::ParseError;
+use adventofcode_2024::input::Input;
+use adventofcode_2024::solver::Part;
+
+/**
+ * What a cached answer was computed from, apart from the build.
+ */
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Key {
+    pub day: u8,
+    pub part: Part,
+    /** See [input_hash]. */
+    pub input_hash: u64,
+    /** The parameters the answer was computed with, as `name=value,...`. */
+    pub params: String,
+}
+
+/**
+ * A cached answer, with the median times it took to parse the input and to
+ * compute the answer.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub answer: String,
+    pub time_parse: Duration,
+    pub time: Duration,
+}
+
+/**
+ * Answers computed by one build of the solvers, stored as tab-separated lines
+ * of day, part, input hash, build id, parameters, parse and solve times in
+ * nanoseconds, and answer. Entries from other builds are dropped when the
+ * cache is loaded, since the code that computed them may have changed.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cache {
+    build_id: String,
+    entries: BTreeMap<Key, Entry>,
+}
+
+impl Cache {
+    pub fn new(build_id: &str) -> Self {
+        Self {
+            build_id: build_id.to_string(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Parse the entries of the `build_id` build from the text of a cache file.
+     * The file is read as written, without [Input] normalization, since a
+     * trailing tab or space is part of the answer.
+     */
+    pub fn parse(source: &str, build_id: &str) -> Result<Self, ParseError> {
+        let mut cache = Self::new(build_id);
+        for (line_num, line) in (1..)
+            .zip(source.lines())
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        {
+            let mut fields = line.splitn(8, '\t');
+            let day = parse_token(line_num, line, fields.next(), "day number")?;
+            let part = parse_token(line_num, line, fields.next(), "'A' or 'B'")?;
+            let input_hash = fields
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(line_num, line, "input hash"))
+                .and_then(|hash| {
+                    u64::from_str_radix(hash, 16)
+                        .map_err(|_| ParseError::at(line_num, line, hash, "input hash"))
+                })?;
+            let entry_build_id = fields
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(line_num, line, "build id"))?;
+            let params = fields
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(line_num, line, "parameters"))?;
+            let time_parse = parse_token(line_num, line, fields.next(), "nanoseconds")?;
+            let time = parse_token(line_num, line, fields.next(), "nanoseconds")?;
+            let answer = fields
+                .next()
+                .ok_or_else(|| ParseError::end_of_line(line_num, line, "answer"))?;
+
+            if entry_build_id == build_id {
+                cache.entries.insert(
+                    Key {
+                        day,
+                        part,
+                        input_hash,
+                        params: params.to_string(),
+                    },
+                    Entry {
+                        answer: answer.to_string(),
+                        time_parse: Duration::from_nanos(time_parse),
+                        time: Duration::from_nanos(time),
+                    },
+                );
+            }
+        }
+        Ok(cache)
+    }
+
+    /**
+     * Load the entries of the `build_id` build from `path`. The cache is empty
+     * if the file does not exist.
+     */
+    pub fn load(path: &Path, build_id: &str) -> Result<Self, std::io::Error> {
+        match std::fs::read_to_string(path) {
+            Ok(source) => Self::parse(&source, build_id)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new(build_id)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+     * Write the entries of this build to `path`, replacing the file. Entries
+     * of other builds that were in the file are not kept.
+     */
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        write_atomically(path, &self.to_string())
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    /**
+     * Store `entry` under `key`, unless the answer contains a tab or line
+     * break and so can't be stored.
+     */
+    pub fn insert(&mut self, key: Key, entry: Entry) {
+        if !entry.answer.contains(['\t', '\n', '\r']) && !key.params.contains(['\t', '\n']) {
+            self.entries.insert(key, entry);
+        }
+    }
+}
+
+impl Display for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "# day\tpart\tinput hash\tbuild id\tparameters\tparse ns\tsolve ns\tanswer"
+        )?;
+        for (key, entry) in &self.entries {
+            writeln!(
+                f,
+                "{}\t{}\t{:016x}\t{}\t{}\t{}\t{}\t{}",
+                key.day,
+                key.part,
+                key.input_hash,
+                self.build_id,
+                key.params,
+                entry.time_parse.as_nanos(),
+                entry.time.as_nanos(),
+                entry.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * A hash of the lines of `input`, which is stable across builds and platforms.
+ */
+pub fn input_hash(input: &Input) -> u64 {
+    input.lines().fold(FNV_OFFSET_BASIS, |hash, line| {
+        fnv1a(fnv1a(hash, line.as_bytes()), b"\n")
+    })
+}
+
+/**
+ * Identifies the running build: the crate version and a hash of the size and
+ * modification time of the executable, which change whenever it is rebuilt.
+ * Only the metadata is read, so this is cheap even for large debug builds.
+ */
+pub fn build_id() -> Result<String, std::io::Error> {
+    let exe = std::fs::metadata(std::env::current_exe()?)?;
+    let mtime = exe
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let hash = fnv1a(FNV_OFFSET_BASIS, &exe.len().to_le_bytes());
+    Ok(format!(
+        "{}+{:016x}",
+        env!("CARGO_PKG_VERSION"),
+        fnv1a(hash, &mtime.to_le_bytes())
+    ))
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::build_id;
+    use super::input_hash;
+    use super::Cache;
+    use super::Entry;
+    use super::Key;
+    use adventofcode_2024::input::Input;
+    use adventofcode_2024::solver::Part;
+
+    fn key(part: Part) -> Key {
+        Key {
+            day: 6,
+            part,
+            input_hash: input_hash(&Input::new("..#\n#^.\n")),
+            params: String::new(),
+        }
+    }
+
+    fn entry(answer: &str) -> Entry {
+        Entry {
+            answer: answer.to_string(),
+            time_parse: Duration::from_nanos(1500),
+            time: Duration::from_millis(250),
+        }
+    }
+
+    #[test]
+    fn input_hash_depends_on_content() {
+        let hash = |s: &str| input_hash(&Input::new(s));
+        assert_eq!(hash("1\n2"), hash("1\n2"));
+        assert_eq!(hash("1\n2"), hash("1\r\n2\n"));
+        assert_ne!(hash("1\n2"), hash("1\n3"));
+        assert_ne!(hash("12"), hash("1\n2"));
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut cache = Cache::new("1.0.0+abc");
+        cache.insert(key(Part::A), entry("41"));
+        cache.insert(
+            Key {
+                params: "size=6".to_string(),
+                ..key(Part::B)
+            },
+            entry("6,1"),
+        );
+        let parsed = Cache::parse(&cache.to_string(), "1.0.0+abc").unwrap();
+        assert_eq!(parsed, cache);
+        assert_eq!(parsed.get(&key(Part::A)), Some(&entry("41")));
+        assert_eq!(parsed.get(&key(Part::B)), None);
+    }
+
+    #[test]
+    fn round_trip_empty_answer() {
+        let mut cache = Cache::new("1.0.0+abc");
+        cache.insert(key(Part::B), entry(""));
+        cache.insert(key(Part::A), entry("12 "));
+        let parsed = Cache::parse(&cache.to_string(), "1.0.0+abc").unwrap();
+        assert_eq!(parsed, cache);
+        assert_eq!(parsed.get(&key(Part::B)), Some(&entry("")));
+        assert_eq!(parsed.get(&key(Part::A)), Some(&entry("12 ")));
+    }
+
+    #[test]
+    fn drops_other_builds() {
+        let mut cache = Cache::new("1.0.0+abc");
+        cache.insert(key(Part::A), entry("41"));
+        let parsed = Cache::parse(&cache.to_string(), "1.0.0+def").unwrap();
+        assert_eq!(parsed.get(&key(Part::A)), None);
+    }
+
+    #[test]
+    fn skips_unstorable_answers() {
+        let mut cache = Cache::new("1.0.0+abc");
+        cache.insert(key(Part::A), entry("#..\n.#."));
+        assert_eq!(cache.get(&key(Part::A)), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(Cache::parse("6\tA\tnot-hex\tb\t\t1\t2\t41\n", "b").is_err());
+        assert!(Cache::parse("6\tA\t00ff\tb\t\t1\n", "b").is_err());
+    }
+
+    #[test]
+    fn build_id_is_stable() {
+        let id = build_id().unwrap();
+        assert!(id.starts_with(concat!(env!("CARGO_PKG_VERSION"), "+")));
+        assert_eq!(build_id().unwrap(), id);
+    }
+}
@@ -0,0 +1,220 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/**
+ * A flag that tells a running solver to give up. Clones share the flag.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/**
+ * The payload of the unwinding started by [check].
+ */
+#[derive(Debug)]
+pub struct Cancelled;
+
+/**
+ * The error of a step that did not finish within its time limit.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TimedOut(pub Duration);
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TIMEOUT after {} s", self.0.as_secs_f64())
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/**
+ * Restores the cancellation token of the current thread when dropped, also
+ * when unwinding.
+ */
+struct RestoreToken(Option<CancelToken>);
+
+impl Drop for RestoreToken {
+    fn drop(&mut self) {
+        CURRENT.set(self.0.take());
+    }
+}
+
+/**
+ * Run `f` with `token` as the cancellation token of the current thread. The
+ * previous token is restored afterwards, even if `f` unwinds.
+ */
+pub fn with_token<T, F>(token: &CancelToken, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let _restore = RestoreToken(CURRENT.replace(Some(token.clone())));
+    f()
+}
+
+/**
+ * Whether the cancellation token of the current thread, if any, has been
+ * cancelled.
+ */
+pub fn is_cancelled() -> bool {
+    CURRENT.with_borrow(|token| token.as_ref().is_some_and(CancelToken::is_cancelled))
+}
+
+/**
+ * Unwind with a [Cancelled] payload if the current thread has been cancelled.
+ * Long-running solvers should call this now and then, for example once per
+ * iteration of their outermost loop.
+ *
+ * The unwinding does not run the panic hook, so nothing is printed.
+ */
+pub fn check() {
+    if is_cancelled() {
+        std::panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+/**
+ * Run `f` on a new thread and wait up to `timeout` for its result. If it takes
+ * longer, its cancellation token is cancelled and the thread is left to stop
+ * by itself, or to run until the process exits if it never calls [check].
+ *
+ * Without a `timeout`, `f` just runs on the current thread.
+ */
+pub fn with_timeout<T, F>(timeout: Option<Duration>, f: F) -> Result<T, TimedOut>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return Ok(f());
+    };
+
+    let token = CancelToken::new();
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let token = token.clone();
+        thread::spawn(move || {
+            // The receiver is gone if the timeout has passed
+            let _ = tx.send(with_token(&token, f));
+        })
+    };
+    match rx.recv_timeout(timeout) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(TimedOut(timeout))
+        }
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("The worker sends a result before it finishes"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::check;
+    use super::is_cancelled;
+    use super::with_timeout;
+    use super::with_token;
+    use super::CancelToken;
+    use super::Cancelled;
+    use super::TimedOut;
+
+    #[test]
+    fn result_arrives_within_timeout() {
+        assert_eq!(
+            with_timeout(Some(Duration::from_secs(10)), || 6 * 7),
+            Ok(42)
+        );
+    }
+
+    #[test]
+    fn runs_on_current_thread_without_timeout() {
+        let id = std::thread::current().id();
+        assert_eq!(
+            with_timeout(None, move || std::thread::current().id() == id),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn timeout_cancels_the_worker() {
+        let (tx, rx) = mpsc::channel();
+        let timeout = Duration::from_millis(20);
+        let outcome = with_timeout(Some(timeout), move || {
+            while !is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            tx.send(()).unwrap();
+        });
+        assert_eq!(outcome, Err(TimedOut(timeout)));
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(()));
+        assert_eq!(outcome.unwrap_err().to_string(), "TIMEOUT after 0.02 s");
+    }
+
+    #[test]
+    fn check_unwinds_only_when_cancelled() {
+        let token = CancelToken::new();
+        assert!(with_token(&token, || catch_unwind(check)).is_ok());
+        token.cancel();
+        let payload = with_token(&token, || catch_unwind(check)).unwrap_err();
+        assert!(payload.is::<Cancelled>());
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn token_is_restored_after_unwinding() {
+        let token = CancelToken::new();
+        token.cancel();
+        let payload = catch_unwind(|| with_token(&token, check)).unwrap_err();
+        assert!(payload.is::<Cancelled>());
+        assert!(!is_cancelled());
+    }
+}
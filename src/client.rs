@@ -0,0 +1,503 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+
+/**
+ * Environment variable holding the session cookie, if no session file is given.
+ */
+pub const SESSION_ENV_VAR: &str = "SESSION_COOKIE";
+
+/** 2024-12-01T00:00:00Z */
+const DECEMBER_1_SECS: u64 = 1_733_011_200;
+
+/**
+ * The website's verdict on a submitted answer.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /** The part was already solved, or part A must be solved first. */
+    WrongLevel,
+    Unrecognized(String),
+}
+
+impl Outcome {
+    /**
+     * Parse the page returned after submitting an answer.
+     */
+    pub fn from_response(body: &str) -> Self {
+        let text = body
+            .split_once("<article>")
+            .and_then(|(_, article)| article.split_once("</article>"))
+            .map(|(article, _)| article)
+            .unwrap_or(body);
+
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Self::TooHigh
+            } else if text.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Self::RateLimited {
+                wait: parse_wait(text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unrecognized(strip_tags(text).trim().to_string())
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too-high"),
+            Self::TooLow => write!(f, "too-low"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate-limited, {} s left to wait", wait.as_secs())
+            }
+            Self::RateLimited { wait: None } => write!(f, "rate-limited"),
+            Self::WrongLevel => write!(f, "wrong level"),
+            Self::Unrecognized(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    /**
+     * Parse the outcomes of evaluated guesses, as written by [Display].
+     */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            _ => Err(format!("Unknown outcome: {s}")),
+        }
+    }
+}
+
+/**
+ * Parse a wait time like "You have 1m 5s left to wait" or "You have 37s left
+ * to wait".
+ */
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|part| {
+            let (num, factor) = if let Some(m) = part.strip_suffix('m') {
+                (m, 60)
+            } else {
+                (part.strip_suffix('s')?, 1)
+            };
+            num.parse::<u64>().ok().map(|n| n * factor)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+fn strip_tags(html: &str) -> String {
+    html.split('<')
+        .enumerate()
+        .map(|(i, s)| {
+            if i == 0 {
+                s
+            } else {
+                s.split_once('>').map(|(_, t)| t).unwrap_or("")
+            }
+        })
+        .collect()
+}
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    Http { status: u16, body: String },
+    Transport(String),
+    InvalidInput(String),
+    Io(std::io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSession => write!(
+                f,
+                "No session cookie: set {SESSION_ENV_VAR} or give a session file"
+            ),
+            Self::Http { status, body } => {
+                write!(
+                    f,
+                    "HTTP status {status}: {}",
+                    body.lines().next().unwrap_or("")
+                )
+            }
+            Self::Transport(message) => write!(f, "Request failed: {message}"),
+            Self::InvalidInput(message) => write!(f, "Invalid puzzle input: {message}"),
+            Self::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/**
+ * Read the session cookie from `session_file` if given, otherwise from the
+ * [SESSION_ENV_VAR] environment variable.
+ */
+pub fn read_session(session_file: Option<&Path>) -> Result<String, ClientError> {
+    let session = match session_file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::env::var(SESSION_ENV_VAR).unwrap_or_default(),
+    };
+    let session = session.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    if session.is_empty() {
+        Err(ClientError::NoSession)
+    } else {
+        Ok(session.to_string())
+    }
+}
+
+/**
+ * The time when the puzzle for `day` unlocks: midnight EST, 05:00 UTC.
+ */
+pub fn unlock_time(day: u8) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(DECEMBER_1_SECS + (u64::from(day) - 1) * 86400 + 5 * 3600)
+}
+
+/**
+ * Sleep until the puzzle for `day` has unlocked, calling `on_wait` with the
+ * remaining time first if there is any.
+ */
+pub fn wait_for_unlock<F>(day: u8, on_wait: F)
+where
+    F: FnOnce(Duration),
+{
+    // Leave a second of margin for clock skew
+    let unlock = unlock_time(day) + Duration::from_secs(1);
+    if let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+        on_wait(remaining);
+        while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+            std::thread::sleep(remaining);
+        }
+    }
+}
+
+/**
+ * Check that `body` looks like a puzzle input rather than an error or login
+ * page.
+ */
+pub fn validate_input(body: &str) -> Result<(), ClientError> {
+    let invalid = |message: &str| Err(ClientError::InvalidInput(message.to_string()));
+    if body.trim().is_empty() {
+        invalid("response is empty")
+    } else if body.trim_start().starts_with('<') {
+        invalid("response is an HTML page")
+    } else if body.contains("Please log in") {
+        invalid("session cookie was not accepted")
+    } else if body.contains("Please don't repeatedly request this endpoint") {
+        invalid("puzzle has not unlocked yet")
+    } else if !body.ends_with('\n') {
+        invalid("response is truncated")
+    } else {
+        Ok(())
+    }
+}
+
+/**
+ * Write `contents` to `path` by writing a temporary file next to it and
+ * renaming it into place, so that `path` is never left partially written.
+ */
+pub fn write_atomically(path: &Path, contents: &str) -> Result<(), std::io::Error> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/**
+ * An authenticated client for the Advent of Code website.
+ */
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new<U, S>(base_url: U, session: S) -> Self
+    where
+        U: Into<String>,
+        S: Into<String>,
+    {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /**
+     * Download and validate the puzzle input for `day`.
+     */
+    pub fn fetch_input(&self, day: u8) -> Result<String, ClientError> {
+        let body = read_response(
+            self.agent
+                .get(&format!("{}/input", self.day_url(day)))
+                .set("Cookie", &self.cookie())
+                .call(),
+        )?;
+        validate_input(&body)?;
+        Ok(body)
+    }
+
+    /**
+     * Submit `answer` to part `level` (1 or 2) of the puzzle for `day`.
+     */
+    pub fn submit_answer(&self, day: u8, level: u8, answer: &str) -> Result<Outcome, ClientError> {
+        let body = read_response(
+            self.agent
+                .post(&format!("{}/answer", self.day_url(day)))
+                .set("Cookie", &self.cookie())
+                .send_form(&[("level", &level.to_string()), ("answer", answer)]),
+        )?;
+        Ok(Outcome::from_response(&body))
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => Err(ClientError::Http {
+            status,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(ureq::Error::Transport(e)) => Err(ClientError::Transport(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unlock_time;
+    use super::validate_input;
+    use super::write_atomically;
+    use super::Client;
+    use super::ClientError;
+    use super::Outcome;
+    use crate::http_mock::MockServer;
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn fetch_input_sends_session_cookie() {
+        let server = MockServer::start(vec![(200, "1 2\n3 4\n")]);
+        let client = Client::new(&server.base_url, "abc123");
+        assert_eq!(client.fetch_input(7).unwrap(), "1 2\n3 4\n");
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2024/day/7/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn fetch_input_rejects_error_responses() {
+        let server = MockServer::start(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (200, "<!DOCTYPE html>\n<html></html>\n"),
+            (
+                200,
+                "Please don't repeatedly request this endpoint before it unlocks!\n",
+            ),
+        ]);
+        let client = Client::new(&server.base_url, "abc123");
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::Http { status: 400, .. })
+        ));
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            client.fetch_input(1),
+            Err(ClientError::InvalidInput(_))
+        ));
+        server.requests();
+    }
+
+    #[test]
+    fn validate_input_requires_complete_text() {
+        assert!(validate_input("12\n").is_ok());
+        assert!(validate_input("").is_err());
+        assert!(validate_input("12").is_err());
+    }
+
+    #[test]
+    fn unlock_time_is_five_am_utc() {
+        assert_eq!(
+            unlock_time(1).duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_secs(1_733_029_200)
+        );
+        assert_eq!(
+            unlock_time(25).duration_since(unlock_time(1)).unwrap(),
+            Duration::from_secs(24 * 86400)
+        );
+    }
+
+    #[test]
+    fn write_atomically_replaces_file() {
+        let dir = std::env::temp_dir().join(format!("aoc-write-test-{}", std::process::id()));
+        let path = dir.join("inputs").join("day01.in");
+        write_atomically(&path, "old\n").unwrap();
+        write_atomically(&path, "new\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn answer_page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn submit_answer_posts_level_and_answer() {
+        let page = answer_page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.");
+        let server = MockServer::start(vec![(200, page)]);
+        let client = Client::new(&server.base_url, "abc123");
+        assert_eq!(
+            client.submit_answer(17, 1, "3,6,3").unwrap(),
+            Outcome::Correct
+        );
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2024/day/17/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc123\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=3%2C6%2C3"));
+    }
+
+    #[test]
+    fn parses_answer_outcomes() {
+        assert_eq!(
+            Outcome::from_response(&answer_page(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::from_response(&answer_page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::from_response(&answer_page(
+                "That's not the right answer.  If you're stuck, ..."
+            )),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::from_response(&answer_page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a>")),
+            Outcome::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(
+            Outcome::from_response(&answer_page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Outcome::WrongLevel
+        );
+        assert_eq!(
+            Outcome::from_response(&answer_page("Something <em>else</em>")),
+            Outcome::Unrecognized("Something else".to_string())
+        );
+    }
+}
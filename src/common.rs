@@ -14,28 +14,392 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::fs::File;
-use std::io::Read;
+use std::fmt::Display;
 use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::input::Block;
+use crate::input::Input;
 
 pub type Solution = (String, String);
 
+/**
+ * A malformed input, located by 1-based line and column numbers.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new<E, F>(line: usize, column: usize, expected: E, found: F) -> Self
+    where
+        E: Into<String>,
+        F: Into<String>,
+    {
+        Self {
+            day: 0,
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /**
+     * An error at `token`, which must be a substring of `line`.
+     */
+    pub fn at<E>(line_num: usize, line: &str, token: &str, expected: E) -> Self
+    where
+        E: Into<String>,
+    {
+        Self::new(
+            line_num,
+            column_of(line, token),
+            expected,
+            format!("{token:?}"),
+        )
+    }
+
+    /**
+     * An error at the end of `line`.
+     */
+    pub fn end_of_line<E>(line_num: usize, line: &str, expected: E) -> Self
+    where
+        E: Into<String>,
+    {
+        Self::new(line_num, line.chars().count() + 1, expected, "end of line")
+    }
+
+    /**
+     * An error after the last line of `input`.
+     */
+    pub fn end_of_input<E>(input: &Input, expected: E) -> Self
+    where
+        E: Into<String>,
+    {
+        Self::new(input.line_count() + 1, 1, expected, "end of input")
+    }
+
+    /**
+     * An error on the empty line or end of input after `block`.
+     */
+    pub fn end_of_block<E>(block: &Block, expected: E) -> Self
+    where
+        E: Into<String>,
+    {
+        Self::new(
+            block.first_line() + block.lines().count(),
+            1,
+            expected,
+            "end of block",
+        )
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        Self { day, ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.day != 0 {
+            write!(f, "Day {}, ", self.day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn column_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|offset| *offset <= line.len())
+        .map(|offset| line[..offset].chars().count() + 1)
+        .unwrap_or(1)
+}
+
+/**
+ * Parse `token`, a substring of `line`, or report where parsing failed. A
+ * `None` token is reported as a premature end of line.
+ */
+pub fn parse_token<T>(
+    line_num: usize,
+    line: &str,
+    token: Option<&str>,
+    expected: &str,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+{
+    let token = token.ok_or_else(|| ParseError::end_of_line(line_num, line, expected))?;
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line_num, line, token, expected))
+}
+
+/**
+ * Check that the `lines` of a grid, paired with their line numbers, all have
+ * the same number of characters, and return that number.
+ */
+pub fn check_rectangular<'a, I>(lines: I) -> Result<usize, ParseError>
+where
+    I: IntoIterator<Item = (usize, &'a str)>,
+{
+    let mut width = None;
+    for (line_num, line) in lines {
+        check_width(
+            line_num,
+            line,
+            *width.get_or_insert_with(|| line.chars().count()),
+        )?;
+    }
+    Ok(width.unwrap_or(0))
+}
+
+/**
+ * Check that `block` has `rows` lines of `cols` characters each.
+ */
+pub fn check_block_shape(block: &Block, rows: usize, cols: usize) -> Result<(), ParseError> {
+    let mut row_count = 0;
+    for (line_num, line) in block.numbered_lines() {
+        if row_count == rows {
+            return Err(ParseError::at(
+                line_num,
+                line,
+                line,
+                format!("empty line after {rows} rows"),
+            ));
+        }
+        check_width(line_num, line, cols)?;
+        row_count += 1;
+    }
+    if row_count < rows {
+        return Err(ParseError::end_of_block(block, format!("{rows} rows")));
+    }
+    Ok(())
+}
+
+/**
+ * Check that `line` has exactly `width` characters.
+ */
+pub(crate) fn check_width(line_num: usize, line: &str, width: usize) -> Result<(), ParseError> {
+    let expected = || format!("{width} columns");
+    match line.char_indices().nth(width) {
+        Some((i, _)) => Err(ParseError::at(line_num, line, &line[i..], expected())),
+        None if line.chars().count() < width => {
+            Err(ParseError::end_of_line(line_num, line, expected()))
+        }
+        None => Ok(()),
+    }
+}
+
+/**
+ * What [normalize] changed in a text.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Normalization {
+    /** Whether a leading UTF-8 byte order mark was removed. */
+    pub bom: bool,
+    /** The number of CRLF line endings replaced with LF. */
+    pub crlf: usize,
+    /** The number of lines stripped of trailing spaces and tabs. */
+    pub trailing_whitespace: usize,
+}
+
+impl Normalization {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        let mut changes = Vec::new();
+        if self.bom {
+            changes.push("removed byte order mark".to_string());
+        }
+        if self.crlf > 0 {
+            changes.push(format!(
+                "converted {} CRLF line ending{}",
+                self.crlf,
+                plural(self.crlf)
+            ));
+        }
+        if self.trailing_whitespace > 0 {
+            changes.push(format!(
+                "trimmed trailing whitespace on {} line{}",
+                self.trailing_whitespace,
+                plural(self.trailing_whitespace)
+            ));
+        }
+        if changes.is_empty() {
+            write!(f, "unchanged")
+        } else {
+            write!(f, "{}", changes.join(", "))
+        }
+    }
+}
+
+/**
+ * Remove a leading byte order mark from `text`, replace CRLF line endings with
+ * LF and strip trailing spaces and tabs from every line. Returns `text`
+ * without copying it if none of those were found.
+ */
+pub fn normalize(text: String) -> (String, Normalization) {
+    let is_dirty = |line: &str| line.ends_with([' ', '\t', '\r']);
+    if !text.starts_with('\u{feff}') && !text.split('\n').any(is_dirty) {
+        return (text, Normalization::default());
+    }
+
+    let mut normalization = Normalization::default();
+    let body = match text.strip_prefix('\u{feff}') {
+        Some(body) => {
+            normalization.bom = true;
+            body
+        }
+        None => &text,
+    };
+    let mut normalized = String::with_capacity(body.len());
+    for (i, line) in body.split('\n').enumerate() {
+        if i > 0 {
+            normalized.push('\n');
+        }
+        let without_cr = line.strip_suffix('\r').unwrap_or(line);
+        if without_cr.len() < line.len() {
+            normalization.crlf += 1;
+        }
+        let trimmed = without_cr.trim_end_matches([' ', '\t']);
+        if trimmed.len() < without_cr.len() {
+            normalization.trailing_whitespace += 1;
+        }
+        normalized.push_str(trimmed);
+    }
+    (normalized, normalization)
+}
+
 pub fn day_input_filename(day: u8) -> PathBuf {
     let padded_day = format!("{day:02}");
     Path::new("inputs").join(format!("day{padded_day}.in"))
 }
 
-pub fn get_file_lines(path: &Path) -> Result<Vec<String>, std::io::Error> {
-    if path == Path::new("-") {
-        read_lines(std::io::stdin())
-    } else {
-        read_lines(File::open(path).unwrap_or_else(|_| panic!("Input file not found: {path:?}")))
+#[cfg(test)]
+mod tests {
+    use super::check_block_shape;
+    use super::check_rectangular;
+    use super::normalize;
+    use super::parse_token;
+    use super::Normalization;
+    use super::ParseError;
+    use crate::input::Input;
+
+    #[test]
+    fn parse_token_reports_column_of_token() {
+        let line = "12 3x 45";
+        let token = line.split_whitespace().nth(1);
+        assert_eq!(
+            parse_token::<u32>(7, line, token, "integer"),
+            Err(ParseError::new(7, 4, "integer", "\"3x\""))
+        );
+    }
+
+    #[test]
+    fn parse_token_reports_missing_token_at_end_of_line() {
+        assert_eq!(
+            parse_token::<u32>(2, "12", None, "integer"),
+            Err(ParseError::new(2, 3, "integer", "end of line"))
+        );
+    }
+
+    #[test]
+    fn display_includes_day_when_set() {
+        let err = ParseError::new(3, 5, "digit", "'x'");
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: expected digit, found 'x'"
+        );
+        assert_eq!(
+            err.in_day(9).to_string(),
+            "Day 9, line 3, column 5: expected digit, found 'x'"
+        );
     }
-}
 
-fn read_lines<I: Read>(mut source: I) -> Result<Vec<String>, std::io::Error> {
-    let mut contents: String = String::new();
-    source.read_to_string(&mut contents)?;
-    Ok(contents.lines().map(&str::to_string).collect())
+    #[test]
+    fn normalize_leaves_clean_text_alone() {
+        let (text, normalization) = normalize("ab\ncd\n\nef\n".to_string());
+        assert_eq!(text, "ab\ncd\n\nef\n");
+        assert!(normalization.is_empty());
+    }
+
+    #[test]
+    fn normalize_reports_changes() {
+        let (text, normalization) = normalize("\u{feff}ab \r\ncd\r\n\t\r\nef\t".to_string());
+        assert_eq!(text, "ab\ncd\n\nef");
+        assert_eq!(
+            normalization,
+            Normalization {
+                bom: true,
+                crlf: 3,
+                trailing_whitespace: 3,
+            }
+        );
+        assert_eq!(
+            normalization.to_string(),
+            "removed byte order mark, converted 3 CRLF line endings, trimmed trailing whitespace on 3 lines"
+        );
+    }
+
+    #[test]
+    fn check_rectangular_reports_ragged_rows() {
+        let lines = |s: &'static str| s.lines().enumerate().map(|(i, line)| (i + 1, line));
+        assert_eq!(check_rectangular(lines("abc\ndef")), Ok(3));
+        assert_eq!(check_rectangular(lines("")), Ok(0));
+        assert_eq!(
+            check_rectangular(lines("abc\nde\nfgh")),
+            Err(ParseError::new(2, 3, "3 columns", "end of line"))
+        );
+        assert_eq!(
+            check_rectangular(lines("abc\ndefg")),
+            Err(ParseError::new(2, 4, "3 columns", "\"g\""))
+        );
+        assert_eq!(check_rectangular(lines("··#\nabc")), Ok(3));
+        assert_eq!(
+            check_rectangular(lines("··#\nabcde")),
+            Err(ParseError::new(2, 4, "3 columns", "\"de\""))
+        );
+        assert_eq!(
+            check_rectangular(lines("abc\n··#·")),
+            Err(ParseError::new(2, 4, "3 columns", "\"·\""))
+        );
+    }
+
+    #[test]
+    fn check_block_shape_reports_missing_and_extra_rows() {
+        let input = Input::new("ab\ncd\n\nab\n\nab\ncd\nef\n");
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(check_block_shape(&blocks[0], 2, 2), Ok(()));
+        assert_eq!(
+            check_block_shape(&blocks[1], 2, 2),
+            Err(ParseError::new(5, 1, "2 rows", "end of block"))
+        );
+        assert_eq!(
+            check_block_shape(&blocks[2], 2, 2),
+            Err(ParseError::new(8, 1, "empty line after 2 rows", "\"ef\""))
+        );
+        assert_eq!(
+            check_block_shape(&blocks[0], 2, 3),
+            Err(ParseError::new(1, 3, "3 columns", "end of line"))
+        );
+    }
 }
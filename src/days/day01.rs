@@ -1,4 +1,9 @@
-use crate::{common::Solution, util::iter::Countable};
+use crate::{
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::iter::Countable,
+};
 
 fn solve_a(left: &[i32], right: &[i32]) -> u32 {
     left.iter()
@@ -14,25 +19,56 @@ fn solve_b(left: &[i32], right: &[i32]) -> i32 {
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (mut left, mut right) = lines.iter().filter(|line| !line.is_empty()).fold(
-        (
-            Vec::with_capacity(lines.len()),
-            Vec::with_capacity(lines.len()),
-        ),
-        |(mut left, mut right), line| {
-            let mut it = line.split_whitespace();
-            left.push(it.next().unwrap().parse().unwrap());
-            right.push(it.next().unwrap().parse().unwrap());
-            (left, right)
-        },
-    );
-
-    left.sort();
-    right.sort();
-
-    (
-        solve_a(&left, &right).to_string(),
-        solve_b(&left, &right).to_string(),
-    )
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+",
+    part_a: Some("11"),
+    part_b: Some("31"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Vec<i32>, Vec<i32>);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let (mut left, mut right) = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .try_fold(
+                (
+                    Vec::with_capacity(input.line_count()),
+                    Vec::with_capacity(input.line_count()),
+                ),
+                |(mut left, mut right), (line_num, line)| {
+                    let mut it = line.split_whitespace();
+                    left.push(parse_token(line_num, line, it.next(), "location ID")?);
+                    right.push(parse_token(line_num, line, it.next(), "location ID")?);
+                    Ok::<_, ParseError>((left, right))
+                },
+            )?;
+
+        left.sort();
+        right.sort();
+
+        Ok((left, right))
+    }
+
+    fn part_a(&self, (left, right): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(left, right).to_string()
+    }
+
+    fn part_b(&self, (left, right): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(left, right).to_string()
+    }
 }
@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{common::Solution, util::iter::WithSliding};
+use crate::{
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::iter::WithSliding,
+};
 
 fn safe<'a, I, F>(expr: F) -> bool
 where
@@ -49,16 +54,45 @@ fn solve_b(reports: &[Vec<i32>]) -> usize {
         .count()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let reports: Vec<Vec<i32>> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect()
-        })
-        .collect();
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+",
+    part_a: Some("2"),
+    part_b: Some("4"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Vec<Vec<i32>>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_num, line)| {
+                line.split_whitespace()
+                    .map(|s| parse_token(line_num, line, Some(s), "level"))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn part_a(&self, reports: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(reports).to_string()
+    }
 
-    (solve_a(&reports).to_string(), solve_b(&reports).to_string())
+    fn part_b(&self, reports: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(reports).to_string()
+    }
 }
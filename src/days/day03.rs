@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 fn eval_muls(s: &str) -> i32 {
     s.split("mul(")
@@ -32,11 +36,11 @@ fn eval_muls(s: &str) -> i32 {
         .sum()
 }
 
-fn solve_a(lines: &[String]) -> i32 {
+fn solve_a(lines: &[&str]) -> i32 {
     lines.iter().map(|line| eval_muls(line)).sum()
 }
 
-fn solve_b(lines: &[String]) -> i32 {
+fn solve_b(lines: &[&str]) -> i32 {
     let (sum, _) = lines
         .iter()
         .flat_map(|line| line.split_inclusive("do()"))
@@ -55,6 +59,42 @@ fn solve_b(lines: &[String]) -> i32 {
     sum
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    (solve_a(lines).to_string(), solve_b(lines).to_string())
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+",
+        part_a: Some("161"),
+        part_b: None,
+        params: &[],
+    },
+    Example {
+        input: "
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+",
+        part_a: None,
+        part_b: Some("48"),
+        params: &[],
+    },
+];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Vec<&'input str>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part_a(&self, lines: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(lines).to_string()
+    }
+
+    fn part_b(&self, lines: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(lines).to_string()
+    }
 }
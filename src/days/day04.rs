@@ -14,52 +14,85 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::{geom::Dir8, grid::Grid},
+};
 
-fn solve_a(grid: &[Vec<char>]) -> usize {
-    (0..grid.len())
-        .flat_map(|r| (0..grid[r].len()).map(move |c| (r as isize, c as isize)))
-        .map(|(r, c)| {
-            (-1_isize..=1)
-                .flat_map(|dr| (-1_isize..=1).map(move |dc| (dr, dc)))
-                .filter(|drc| *drc != (0, 0))
-                .filter(|(dr, dc)| {
-                    (0..grid.len() as isize).contains(&(r + 3 * dr))
-                        && (0..grid[0].len() as isize).contains(&(c + 3 * dc))
-                })
-                .filter(|(dr, dc)| {
-                    grid[r as usize][c as usize] == 'X'
-                        && grid[(r + dr) as usize][(c + dc) as usize] == 'M'
-                        && grid[(r + 2 * dr) as usize][(c + 2 * dc) as usize] == 'A'
-                        && grid[(r + 3 * dr) as usize][(c + 3 * dc) as usize] == 'S'
+fn solve_a(grid: &Grid<char>) -> usize {
+    grid.find_all(&'X')
+        .map(|pos| {
+            Dir8::ALL
+                .into_iter()
+                .filter(|dir| {
+                    "MAS"
+                        .chars()
+                        .try_fold(pos, |pos, ch| {
+                            grid.offset(pos, *dir).filter(|next| grid[*next] == ch)
+                        })
+                        .is_some()
                 })
                 .count()
         })
         .sum()
 }
 
-fn solve_b(grid: &[Vec<char>]) -> usize {
-    (1..grid.len() - 1)
-        .flat_map(|r| (1..grid[r].len() - 1).map(move |c| (r, c)))
-        .filter(|(r, c)| {
-            let tl = grid[r - 1][c - 1];
-            grid[*r][*c] == 'A' && {
-                let tr = grid[r - 1][c + 1];
-                let bl = grid[r + 1][c - 1];
-                let br = grid[r + 1][c + 1];
+fn solve_b(grid: &Grid<char>) -> usize {
+    grid.find_all(&'A')
+        .filter(|pos| {
+            let corner = |step| grid.offset(*pos, step).map(|corner| grid[corner]);
+            let tl = corner((-1, -1));
+            let tr = corner((-1, 1));
+            let bl = corner((1, -1));
+            let br = corner((1, 1));
 
-                ((tl == 'M' && br == 'S') || (tl == 'S' && br == 'M'))
-                    && ((bl == 'M' && tr == 'S') || (bl == 'S' && tr == 'M'))
-            }
+            ((tl == Some('M') && br == Some('S')) || (tl == Some('S') && br == Some('M')))
+                && ((bl == Some('M') && tr == Some('S')) || (bl == Some('S') && tr == Some('M')))
         })
         .count()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let grid: Vec<Vec<char>> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect();
-    (solve_a(&grid).to_string(), solve_b(&grid).to_string())
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+",
+    part_a: Some("18"),
+    part_b: Some("9"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Grid<char>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let grid = input.grid("any character", Some)?;
+        if grid.is_empty() {
+            return Err(ParseError::end_of_input(input, "letter grid"));
+        }
+        Ok(grid)
+    }
+
+    fn part_a(&self, grid: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(grid).to_string()
+    }
+
+    fn part_b(&self, grid: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(grid).to_string()
+    }
 }
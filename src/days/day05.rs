@@ -16,104 +16,139 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::common::Solution;
+use crate::{
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
-fn solve_a(lines: &[String]) -> u32 {
-    let rules: HashMap<u32, HashSet<u32>> = lines
-        .iter()
-        .take_while(|line| line.contains('|'))
-        .map(|line| {
-            let mut it = line.split('|').map(|s| s.parse().unwrap());
-            (it.next().unwrap(), it.next().unwrap())
+type Rules = HashMap<u32, HashSet<u32>>;
+
+fn is_ordered(rules: &Rules, seq: &[u32]) -> bool {
+    let seqset: HashSet<u32> = seq.iter().copied().collect();
+    seq.iter()
+        .scan((true, HashSet::new()), |(valid, before), next| {
+            *valid = rules
+                .get(next)
+                .map(|rule| before.is_superset(&rule.intersection(&seqset).copied().collect()))
+                .unwrap_or(true);
+            before.insert(*next);
+            Some(*valid)
         })
-        .fold(HashMap::new(), |mut rules, (before, after)| {
-            rules.entry(after).or_default().insert(before);
-            rules
-        });
-    lines
+        .all(|valid| valid)
+}
+
+fn solve_a(rules: &Rules, updates: &[Vec<u32>]) -> u32 {
+    updates
         .iter()
-        .skip_while(|line| line.contains('|') || line.is_empty())
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let seq: Vec<u32> = line.split(',').map(|s| s.parse().unwrap()).collect();
-            let seqset: HashSet<u32> = seq.iter().copied().collect();
-            if seq
-                .iter()
-                .scan((true, HashSet::new()), |(valid, before), next| {
-                    *valid = rules
-                        .get(next)
-                        .map(|rule| {
-                            before.is_superset(&rule.intersection(&seqset).copied().collect())
-                        })
-                        .unwrap_or(true);
-                    before.insert(*next);
-                    Some(*valid)
-                })
-                .all(|valid| valid)
-            {
-                Some(seq[seq.len() / 2])
-            } else {
-                None
-            }
-        })
+        .filter(|seq| is_ordered(rules, seq))
+        .map(|seq| seq[seq.len() / 2])
         .sum()
 }
 
-fn solve_b(lines: &[String]) -> u32 {
-    let rules: HashMap<u32, HashSet<u32>> = lines
-        .iter()
-        .take_while(|line| line.contains('|'))
-        .map(|line| {
-            let mut it = line.split('|').map(|s| s.parse().unwrap());
-            (it.next().unwrap(), it.next().unwrap())
-        })
-        .fold(HashMap::new(), |mut rules, (before, after)| {
-            rules.entry(after).or_default().insert(before);
-            rules
-        });
-    lines
+fn solve_b(rules: &Rules, updates: &[Vec<u32>]) -> u32 {
+    updates
         .iter()
-        .skip_while(|line| line.contains('|') || line.is_empty())
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let seq: Vec<u32> = line.split(',').map(|s| s.parse().unwrap()).collect();
+        .filter(|seq| !is_ordered(rules, seq))
+        .map(|seq| {
             let seqset: HashSet<u32> = seq.iter().copied().collect();
-            if !seq
-                .iter()
-                .scan((true, HashSet::new()), |(valid, before), next| {
-                    *valid = rules
-                        .get(next)
-                        .map(|rule| {
-                            before.is_superset(&rule.intersection(&seqset).copied().collect())
-                        })
-                        .unwrap_or(true);
-                    before.insert(*next);
-                    Some(*valid)
-                })
-                .all(|valid| valid)
-            {
-                let sortedseq: Vec<u32> =
-                    seqset.into_iter().fold(Vec::new(), |mut sorted, next| {
-                        if let Some((insert_i, _)) = sorted.iter().enumerate().find(|(_, el)| {
-                            rules
-                                .get(el)
-                                .map(|rule| rule.contains(&next))
-                                .unwrap_or(false)
-                        }) {
-                            sorted.insert(insert_i, next);
-                        } else {
-                            sorted.push(next)
-                        }
-                        sorted
-                    });
-                Some(sortedseq[sortedseq.len() / 2])
-            } else {
-                None
-            }
+            let sortedseq: Vec<u32> = seqset.into_iter().fold(Vec::new(), |mut sorted, next| {
+                if let Some((insert_i, _)) = sorted.iter().enumerate().find(|(_, el)| {
+                    rules
+                        .get(el)
+                        .map(|rule| rule.contains(&next))
+                        .unwrap_or(false)
+                }) {
+                    sorted.insert(insert_i, next);
+                } else {
+                    sorted.push(next)
+                }
+                sorted
+            });
+            sortedseq[sortedseq.len() / 2]
         })
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    (solve_a(lines).to_string(), solve_b(lines).to_string())
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+",
+    part_a: Some("143"),
+    part_b: Some("123"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Rules, Vec<Vec<u32>>);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let mut blocks = input.blocks();
+        let rules: Rules = blocks
+            .next()
+            .into_iter()
+            .flat_map(|block| block.numbered_lines())
+            .map(|(line_num, line)| {
+                let mut it = line.split('|');
+                Ok((
+                    parse_token(line_num, line, it.next(), "page number")?,
+                    parse_token(line_num, line, it.next(), "page number")?,
+                ))
+            })
+            .collect::<Result<Vec<(u32, u32)>, ParseError>>()?
+            .into_iter()
+            .fold(HashMap::new(), |mut rules, (before, after)| {
+                rules.entry(after).or_default().insert(before);
+                rules
+            });
+        let updates: Vec<Vec<u32>> = blocks
+            .flat_map(|block| block.numbered_lines())
+            .map(|(line_num, line)| {
+                line.split(',')
+                    .map(|s| parse_token(line_num, line, Some(s), "page number"))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((rules, updates))
+    }
+
+    fn part_a(&self, (rules, updates): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(rules, updates).to_string()
+    }
+
+    fn part_b(&self, (rules, updates): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(rules, updates).to_string()
+    }
 }
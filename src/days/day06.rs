@@ -16,13 +16,21 @@
 
 use std::collections::HashSet;
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::{
+        geom::{Dir4, Pos},
+        grid::Grid,
+    },
+};
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Pos {
-    r: usize,
-    c: usize,
-    dir: u8,
+pub struct Guard {
+    pos: Pos,
+    dir: Dir4,
 }
 
 struct Map {
@@ -31,10 +39,10 @@ struct Map {
 }
 
 impl Map {
-    fn new(map: &[Vec<bool>]) -> Self {
+    fn new(map: &Grid<bool>) -> Self {
         Self {
             obstacles_r: map
-                .iter()
+                .rows()
                 .map(|row| {
                     row.iter()
                         .enumerate()
@@ -43,8 +51,14 @@ impl Map {
                         .collect()
                 })
                 .collect(),
-            obstacles_c: (0..map[0].len())
-                .map(|c| (0..map.len()).filter(|r| map[*r][c]).collect())
+            obstacles_c: (0..map.width())
+                .map(|c| {
+                    map.column(c)
+                        .enumerate()
+                        .filter(|(_, cell)| **cell)
+                        .map(|(r, _)| r)
+                        .collect()
+                })
                 .collect(),
         }
     }
@@ -66,76 +80,62 @@ impl Map {
         }
     }
 
-    fn seek(&self, pos: &Pos) -> Option<Pos> {
-        let (rr, cc) = match pos.dir {
-            0 => {
-                let ri = self.obstacles_c[pos.c].partition_point(|rr| *rr < pos.r);
-                let rr = self.obstacles_c[pos.c].get(ri.checked_sub(1)?)? + 1;
-                Some((rr, pos.c)).filter(|(rr, _)| *rr <= pos.r)
+    fn seek(&self, guard: &Guard) -> Option<Guard> {
+        let Pos { r, c } = guard.pos;
+        let pos = match guard.dir {
+            Dir4::Up => {
+                let ri = self.obstacles_c[c].partition_point(|rr| *rr < r);
+                let rr = self.obstacles_c[c].get(ri.checked_sub(1)?)? + 1;
+                Some(Pos::new(rr, c)).filter(|pos| pos.r <= r)
             }
-            1 => {
-                let ci = self.obstacles_r[pos.r].partition_point(|cc| *cc <= pos.c);
-                let cc = self.obstacles_r[pos.r].get(ci)? - 1;
-                Some((pos.r, cc)).filter(|(_, cc)| *cc >= pos.c)
+            Dir4::Right => {
+                let ci = self.obstacles_r[r].partition_point(|cc| *cc <= c);
+                let cc = self.obstacles_r[r].get(ci)? - 1;
+                Some(Pos::new(r, cc)).filter(|pos| pos.c >= c)
             }
-            2 => {
-                let ri = self.obstacles_c[pos.c].partition_point(|rr| *rr <= pos.r);
-                let rr = self.obstacles_c[pos.c].get(ri)? - 1;
-                Some((rr, pos.c)).filter(|(rr, _)| *rr >= pos.r)
+            Dir4::Down => {
+                let ri = self.obstacles_c[c].partition_point(|rr| *rr <= r);
+                let rr = self.obstacles_c[c].get(ri)? - 1;
+                Some(Pos::new(rr, c)).filter(|pos| pos.r >= r)
             }
-            3 => {
-                let ci = self.obstacles_r[pos.r].partition_point(|cc| *cc < pos.c);
-                let cc = self.obstacles_r[pos.r].get(ci.checked_sub(1)?)? + 1;
-                Some((pos.r, cc)).filter(|(_, cc)| *cc <= pos.c)
+            Dir4::Left => {
+                let ci = self.obstacles_r[r].partition_point(|cc| *cc < c);
+                let cc = self.obstacles_r[r].get(ci.checked_sub(1)?)? + 1;
+                Some(Pos::new(r, cc)).filter(|pos| pos.c <= c)
             }
-            _ => unreachable!(),
         }?;
-        Some(Pos {
-            r: rr,
-            c: cc,
-            dir: (pos.dir + 1) % 4,
+        Some(Guard {
+            pos,
+            dir: guard.dir.turn_right(),
         })
     }
 }
 
-fn step(Pos { r, c, dir }: &Pos) -> Option<Pos> {
-    let (rr, cc) = match dir {
-        0 => (r.checked_sub(1)?, *c),
-        1 => (*r, c + 1),
-        2 => (r + 1, *c),
-        3 => (*r, c.checked_sub(1)?),
-        _ => unreachable!(),
-    };
-    Some(Pos {
-        r: rr,
-        c: cc,
-        dir: *dir,
+fn step(guard: &Guard) -> Option<Guard> {
+    Some(Guard {
+        pos: guard.pos.checked_add(guard.dir.delta())?,
+        dir: guard.dir,
     })
 }
 
-fn trace_path(map: &[Vec<bool>], start: Pos) -> (Vec<Pos>, bool) {
+fn trace_path(map: &Grid<bool>, start: Guard) -> (Vec<Guard>, bool) {
     let mut is_loop = false;
     let mut visited = HashSet::new();
     (
-        std::iter::successors(Some(start), |pos @ Pos { r, c, dir }| {
-            if visited.contains(pos) {
+        std::iter::successors(Some(start), |guard| {
+            if visited.contains(guard) {
                 is_loop = true;
                 None
             } else {
-                visited.insert(*pos);
-                let Pos { r: rr, c: cc, .. } = step(pos)?;
-                Some(if *map.get(rr)?.get(cc)? {
-                    Pos {
-                        r: *r,
-                        c: *c,
-                        dir: (dir + 1) % 4,
+                visited.insert(*guard);
+                let next = step(guard)?;
+                Some(if *map.get(next.pos)? {
+                    Guard {
+                        pos: guard.pos,
+                        dir: guard.dir.turn_right(),
                     }
                 } else {
-                    Pos {
-                        r: rr,
-                        c: cc,
-                        dir: *dir,
-                    }
+                    next
                 })
             }
         })
@@ -144,40 +144,41 @@ fn trace_path(map: &[Vec<bool>], start: Pos) -> (Vec<Pos>, bool) {
     )
 }
 
-fn is_loop(map: &Map, start: Pos) -> bool {
+fn is_loop(map: &Map, start: Guard) -> bool {
     let mut visited = HashSet::new();
-    let mut pos = start;
-    while let Some(new_pos) = map.seek(&pos) {
-        if visited.contains(&new_pos) {
+    let mut guard = start;
+    while let Some(next) = map.seek(&guard) {
+        if visited.contains(&next) {
             return true;
         } else {
-            visited.insert(new_pos);
-            pos = new_pos;
+            visited.insert(next);
+            guard = next;
         }
     }
     false
 }
 
-fn solve_a(path: &[Pos]) -> usize {
+fn solve_a(path: &[Guard]) -> usize {
     path.iter()
-        .map(|Pos { r, c, .. }| (r, c))
+        .map(|guard| guard.pos)
         .collect::<HashSet<_>>()
         .len()
 }
 
-fn solve_b(map: Vec<Vec<bool>>, start: Pos, path: &[Pos]) -> usize {
-    let obstacle_map = Map::new(&map);
-    let candidate_coords: HashSet<(usize, usize)> = path
+fn solve_b(map: &Grid<bool>, start: Guard, path: &[Guard]) -> usize {
+    let obstacle_map = Map::new(map);
+    let candidate_coords: HashSet<Pos> = path
         .iter()
         .flat_map(step)
-        .filter(|Pos { r, c, .. }| *r < map.len() && *c < map[0].len())
-        .map(|Pos { r, c, .. }| (r, c))
+        .map(|guard| guard.pos)
+        .filter(|pos| map.contains(*pos))
         .collect();
     candidate_coords
         .into_iter()
-        .filter(|(r, c)| {
-            if !map[*r][*c] {
-                is_loop(&obstacle_map.with_obstacle(*r, *c), start)
+        .filter(|pos| {
+            cancel::check();
+            if !map[*pos] {
+                is_loop(&obstacle_map.with_obstacle(pos.r, pos.c), start)
             } else {
                 false
             }
@@ -185,36 +186,53 @@ fn solve_b(map: Vec<Vec<bool>>, start: Pos, path: &[Pos]) -> usize {
         .count()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (start, map): (Option<Pos>, Vec<Vec<bool>>) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .fold(
-            (None, Vec::with_capacity(lines.len())),
-            |(mut start, mut map), (r, line)| {
-                map.push(
-                    line.chars()
-                        .enumerate()
-                        .map(|(c, ch)| match ch {
-                            '#' => true,
-                            '^' => {
-                                start = Some(Pos { r, c, dir: 0 });
-                                false
-                            }
-                            _ => false,
-                        })
-                        .collect(),
-                );
-                (start, map)
-            },
-        );
-    let start = start.expect("Failed to find start position");
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+",
+    part_a: Some("41"),
+    part_b: Some("6"),
+    params: &[],
+}];
 
-    let (path, _) = trace_path(&map, start);
+pub struct Day;
 
-    (
-        solve_a(&path).to_string(),
-        solve_b(map, start, &path).to_string(),
-    )
+impl Solver for Day {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Grid<bool>, Guard);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let map = input.grid("'.', '#' or '^'", |ch| {
+            Some(ch).filter(|ch| ".#^".contains(*ch))
+        })?;
+        let pos = map
+            .find(&'^')
+            .ok_or_else(|| ParseError::end_of_input(input, "start position '^'"))?;
+        let start = Guard {
+            pos: pos.into(),
+            dir: Dir4::Up,
+        };
+        Ok((map.map(|ch| *ch == '#'), start))
+    }
+
+    fn part_a(&self, (map, start): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (path, _) = trace_path(map, *start);
+        solve_a(&path).to_string()
+    }
+
+    fn part_b(&self, (map, start): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (path, _) = trace_path(map, *start);
+        solve_b(map, *start, &path).to_string()
+    }
 }
@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 type Equation = (u64, Vec<u64>);
 
@@ -49,29 +54,68 @@ fn solve_a(equations: &[Equation]) -> (Vec<&Equation>, Vec<&Equation>) {
 fn solve_b(equations: &[&Equation]) -> u64 {
     equations
         .iter()
-        .filter(|(lhs, rhs)| can_solve::<true>(*lhs, rhs[0], &rhs[1..]))
+        .filter(|(lhs, rhs)| {
+            cancel::check();
+            can_solve::<true>(*lhs, rhs[0], &rhs[1..])
+        })
         .map(|(lhs, _)| lhs)
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let equations: Vec<Equation> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (lhs, rhs) = line.split_once(':').unwrap();
-            (
-                lhs.parse().unwrap(),
-                rhs.split_whitespace().map(|s| s.parse().unwrap()).collect(),
-            )
-        })
-        .collect();
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+",
+    part_a: Some("3749"),
+    part_b: Some("11387"),
+    params: &[],
+}];
 
-    let (sol, unsol) = solve_a(&equations);
-    let solution_a: u64 = sol.iter().map(|(lhs, _)| lhs).sum();
+pub struct Day;
 
-    (
-        solution_a.to_string(),
-        (solution_a + solve_b(&unsol)).to_string(),
-    )
+impl Solver for Day {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Vec<Equation>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let equations: Vec<Equation> = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_num, line)| {
+                let (lhs, rhs) = line
+                    .split_once(':')
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "':'"))?;
+                let rhs: Vec<u64> = rhs
+                    .split_whitespace()
+                    .map(|s| parse_token(line_num, line, Some(s), "operand"))
+                    .collect::<Result<_, _>>()?;
+                if rhs.is_empty() {
+                    return Err(ParseError::end_of_line(line_num, line, "operand"));
+                }
+                Ok((parse_token(line_num, line, Some(lhs), "test value")?, rhs))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(equations)
+    }
+
+    fn part_a(&self, equations: &Self::Parsed<'_>, _params: &Params) -> String {
+        let (sol, _) = solve_a(equations);
+        sol.iter().map(|(lhs, _)| lhs).sum::<u64>().to_string()
+    }
+
+    fn part_b(&self, equations: &Self::Parsed<'_>, _params: &Params) -> String {
+        let (sol, unsol) = solve_a(equations);
+        let solution_a: u64 = sol.iter().map(|(lhs, _)| lhs).sum();
+        (solution_a + solve_b(&unsol)).to_string()
+    }
 }
@@ -16,15 +16,16 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::{common::Solution, util::iter::WithPairs};
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::iter::WithPairs,
+};
 
-fn solve_ab(
-    map: &HashMap<char, Vec<(isize, isize)>>,
-    maxr: isize,
-    maxc: isize,
-    skip: usize,
-    take: usize,
-) -> usize {
+type Antennae = HashMap<char, Vec<(isize, isize)>>;
+
+fn solve_ab(map: &Antennae, maxr: isize, maxc: isize, skip: usize, take: usize) -> usize {
     map.values()
         .flat_map(|antennae| {
             antennae.pairs().flat_map(|((ra, ca), (rb, cb))| {
@@ -44,25 +45,62 @@ fn solve_ab(
         .len()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let map: HashMap<char, Vec<(isize, isize)>> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .flat_map(|(r, row)| {
-            row.chars()
-                .enumerate()
-                .filter(|(_, freq)| *freq != '.')
-                .map(move |(c, freq)| (r as isize, c as isize, freq))
-        })
-        .fold(HashMap::new(), |mut map, (r, c, freq)| {
-            map.entry(freq).or_default().push((r, c));
-            map
-        });
-    let maxr = lines.iter().filter(|line| !line.is_empty()).count() as isize;
-    let maxc = lines[0].len() as isize;
-    (
-        solve_ab(&map, maxr, maxc, 1, 1).to_string(),
-        solve_ab(&map, maxr, maxc, 0, usize::MAX).to_string(),
-    )
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+",
+    part_a: Some("14"),
+    part_b: Some("34"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Antennae, isize, isize);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let map: Antennae = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, freq)| *freq != '.')
+                    .map(move |(c, freq)| (r as isize, c as isize, freq))
+            })
+            .fold(HashMap::new(), |mut map, (r, c, freq)| {
+                map.entry(freq).or_default().push((r, c));
+                map
+            });
+        let maxr = input.lines().filter(|line| !line.is_empty()).count() as isize;
+        if maxr == 0 {
+            return Err(ParseError::end_of_input(input, "antenna map"));
+        }
+        let maxc = input.grid_width()? as isize;
+        Ok((map, maxr, maxc))
+    }
+
+    fn part_a(&self, (map, maxr, maxc): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_ab(map, *maxr, *maxc, 1, 1).to_string()
+    }
+
+    fn part_b(&self, (map, maxr, maxc): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_ab(map, *maxr, *maxc, 0, usize::MAX).to_string()
+    }
 }
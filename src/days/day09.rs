@@ -19,10 +19,14 @@ use std::{
     collections::BinaryHeap,
 };
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Fragment {
+pub struct Fragment {
     id: usize,
     start: usize,
     len: usize,
@@ -40,10 +44,9 @@ impl PartialOrd for Fragment {
     }
 }
 
-fn defragment(
-    mut files: Vec<Fragment>,
-    mut gaps: [BinaryHeap<Reverse<usize>>; 10],
-) -> Vec<Fragment> {
+type Gaps = [BinaryHeap<Reverse<usize>>; 10];
+
+fn defragment(mut files: Vec<Fragment>, mut gaps: Gaps) -> Vec<Fragment> {
     for file in files.iter_mut().rev() {
         if let Some((len, start)) = gaps
             .iter()
@@ -102,42 +105,75 @@ fn solve_a(files: &[Fragment]) -> usize {
     checksum
 }
 
-fn solve_b(files: Vec<Fragment>, gaps: [BinaryHeap<Reverse<usize>>; 10]) -> usize {
+fn solve_b(files: Vec<Fragment>, gaps: Gaps) -> usize {
     defragment(files, gaps)
         .into_iter()
         .map(|f| (f.start..(f.start + f.len)).sum::<usize>() * f.id)
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (_, _, _, files, gaps): (_, _, _, Vec<Fragment>, [BinaryHeap<Reverse<usize>>; 10]) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .flat_map(|line| line.chars())
-        .map(|ch| ch.to_digit(10).unwrap() as usize)
-        .fold(
-            (0, 0, true, Vec::new(), [const { BinaryHeap::new() }; 10]),
-            |(start, mut next_id, is_file, mut files, mut gaps), len| {
-                if len > 0 {
-                    if is_file {
-                        files.push(Fragment {
-                            id: next_id,
-                            start,
-                            len,
-                        });
-                        next_id += 1;
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+2333133121414131402
+",
+    part_a: Some("1928"),
+    part_b: Some("2858"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Vec<Fragment>, Gaps);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let (_, _, _, files, gaps): (_, _, _, Vec<Fragment>, Gaps) = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .flat_map(|(line_num, line)| {
+                line.chars().enumerate().map(move |(c, ch)| {
+                    ch.to_digit(10)
+                        .map(|len| len as usize)
+                        .ok_or_else(|| ParseError::new(line_num, c + 1, "digit", format!("{ch:?}")))
+                })
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?
+            .into_iter()
+            .fold(
+                (0, 0, true, Vec::new(), [const { BinaryHeap::new() }; 10]),
+                |(start, mut next_id, is_file, mut files, mut gaps), len| {
+                    if len > 0 {
+                        if is_file {
+                            files.push(Fragment {
+                                id: next_id,
+                                start,
+                                len,
+                            });
+                            next_id += 1;
+                        } else {
+                            gaps[len].push(Reverse(start));
+                        }
+                        (start + len, next_id, !is_file, files, gaps)
                     } else {
-                        gaps[len].push(Reverse(start));
+                        (start, next_id, !is_file, files, gaps)
                     }
-                    (start + len, next_id, !is_file, files, gaps)
-                } else {
-                    (start, next_id, !is_file, files, gaps)
-                }
-            },
-        );
+                },
+            );
 
-    (
-        solve_a(&files).to_string(),
-        solve_b(files, gaps).to_string(),
-    )
+        if files.is_empty() {
+            return Err(ParseError::end_of_input(input, "disk map"));
+        }
+        Ok((files, gaps))
+    }
+
+    fn part_a(&self, (files, _): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(files).to_string()
+    }
+
+    fn part_b(&self, (files, gaps): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(files.clone(), gaps.clone()).to_string()
+    }
 }
@@ -16,68 +16,81 @@
 
 use std::collections::HashSet;
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::grid::Grid,
+};
 
 fn find_paths<'res>(
-    map: &Vec<Vec<u8>>,
-    (r, c): (usize, usize),
+    map: &Grid<u8>,
+    pos: (usize, usize),
     peaks: &'res mut HashSet<(usize, usize)>,
 ) -> (&'res mut HashSet<(usize, usize)>, usize) {
-    if map[r][c] == 9 {
-        peaks.insert((r, c));
+    if map[pos] == 9 {
+        peaks.insert(pos);
         (peaks, 1)
     } else {
-        let paths = [
-            r.checked_sub(1).map(|rr| (rr, c)),
-            c.checked_sub(1).map(|cc| (r, cc)),
-            Some(r + 1).filter(|rr| *rr < map.len()).map(|rr| (rr, c)),
-            Some(c + 1)
-                .filter(|cc| *cc < map[0].len())
-                .map(|cc| (r, cc)),
-        ]
-        .iter()
-        .flatten()
-        .copied()
-        .filter(|(rr, cc)| map[*rr][*cc] == map[r][c] + 1)
-        .map(|(rr, cc)| {
-            let (_, paths) = find_paths(map, (rr, cc), peaks);
-            paths
-        })
-        .sum();
+        let paths = map
+            .neighbors4(pos)
+            .filter(|next| map[*next] == map[pos] + 1)
+            .map(|next| {
+                let (_, paths) = find_paths(map, next, peaks);
+                paths
+            })
+            .sum();
         (peaks, paths)
     }
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (map, heads): (Vec<Vec<u8>>, Vec<(usize, usize)>) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .fold(
-            (Vec::with_capacity(lines.len()), Vec::new()),
-            |(mut rows, mut heads), (r, line)| {
-                let (tiles, row_heads) = line.chars().enumerate().fold(
-                    (Vec::with_capacity(line.len()), Vec::new()),
-                    |(mut tiles, mut heads), (c, ch)| {
-                        let elevation = ch.to_digit(10).unwrap() as u8;
-                        tiles.push(elevation);
-                        if elevation == 0 {
-                            heads.push((r, c));
-                        }
-                        (tiles, heads)
-                    },
-                );
-                rows.push(tiles);
-                heads.extend(row_heads);
-                (rows, heads)
-            },
-        );
-
-    let (scores, paths) = heads.iter().fold((0, 0), |(scores, paths), pos| {
+fn solve_ab(map: &Grid<u8>, heads: &[(usize, usize)]) -> (usize, usize) {
+    heads.iter().fold((0, 0), |(scores, paths), pos| {
         let mut peaks = HashSet::new();
-        let (peaks, p) = find_paths(&map, *pos, &mut peaks);
+        let (peaks, p) = find_paths(map, *pos, &mut peaks);
         (scores + peaks.len(), paths + p)
-    });
+    })
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+",
+    part_a: Some("36"),
+    part_b: Some("81"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Grid<u8>, Vec<(usize, usize)>);
 
-    (scores.to_string(), paths.to_string())
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let map = input.grid("elevation digit", |ch| {
+            ch.to_digit(10).map(|elevation| elevation as u8)
+        })?;
+        let heads = map.find_all(&0).collect();
+        Ok((map, heads))
+    }
+
+    fn part_a(&self, (map, heads): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (scores, _) = solve_ab(map, heads);
+        scores.to_string()
+    }
+
+    fn part_b(&self, (map, heads): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (_, paths) = solve_ab(map, heads);
+        paths.to_string()
+    }
 }
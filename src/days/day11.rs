@@ -16,14 +16,19 @@
 
 use std::collections::HashMap;
 
-use crate::{common::Solution, util::iter::Countable};
+use crate::{
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Param, Params, Solver},
+    util::iter::Countable,
+};
 
 fn step(stone: u64) -> (u64, Option<u64>) {
     if stone == 0 {
         (1, None)
     } else {
         let log = stone.ilog10() + 1;
-        if log % 2 == 0 {
+        if log.is_multiple_of(2) {
             let divisor = 10_u64.pow(log / 2);
             (stone / divisor, Some(stone % divisor))
         } else {
@@ -48,17 +53,64 @@ fn simulate(mut stones: HashMap<u64, usize>, steps: usize) -> HashMap<u64, usize
     stones
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let stones: HashMap<u64, usize> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .flat_map(|line| line.split_whitespace())
-        .map(|s| s.parse().unwrap())
-        .counts();
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+125 17
+",
+    part_a: Some("55312"),
+    part_b: None,
+    params: &[],
+}];
 
-    let stones = simulate(stones, 25);
-    let solution_a: usize = stones.values().sum();
+const PARAMS: &[Param] = &[
+    Param {
+        name: "blinks_a",
+        default: 25,
+        min: 0,
+        description: "Number of blinks in part A",
+    },
+    Param {
+        name: "blinks_b",
+        default: 75,
+        min: 0,
+        description: "Number of blinks in part B",
+    },
+];
 
-    let solution_b: usize = simulate(stones, 50).values().sum();
-    (solution_a.to_string(), solution_b.to_string())
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = HashMap<u64, usize>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let stones: HashMap<u64, usize> = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .flat_map(|(line_num, line)| {
+                line.split_whitespace()
+                    .map(move |s| parse_token(line_num, line, Some(s), "stone number"))
+            })
+            .collect::<Result<Vec<u64>, ParseError>>()?
+            .into_iter()
+            .counts();
+        Ok(stones)
+    }
+
+    fn part_a(&self, stones: &Self::Parsed<'_>, params: &Params) -> String {
+        simulate(stones.clone(), params.get("blinks_a"))
+            .values()
+            .sum::<usize>()
+            .to_string()
+    }
+
+    fn part_b(&self, stones: &Self::Parsed<'_>, params: &Params) -> String {
+        simulate(stones.clone(), params.get("blinks_b"))
+            .values()
+            .sum::<usize>()
+            .to_string()
+    }
 }
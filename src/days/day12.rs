@@ -16,7 +16,12 @@
 
 use std::collections::{HashSet, VecDeque};
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::{geom::Dir4, grid::Grid},
+};
 
 #[derive(Debug)]
 struct Tile {
@@ -24,27 +29,20 @@ struct Tile {
     neighbors: usize,
 }
 
-struct Map {
-    rows: Vec<Vec<Tile>>,
+pub struct Map {
+    rows: Grid<Tile>,
     regions: Vec<HashSet<(usize, usize)>>,
 }
 
 impl Map {
-    fn displace(&self, r: usize, c: usize, dr: isize, dc: isize) -> Option<(usize, usize)> {
-        r.checked_add_signed(dr)
-            .zip(c.checked_add_signed(dc))
-            .filter(|(rr, cc)| {
-                (0..self.rows.len()).contains(rr) && (0..self.rows[0].len()).contains(cc)
-            })
-    }
-
     fn get_drc(&self, r: usize, c: usize, dr: isize, dc: isize) -> Option<&Tile> {
-        self.displace(r, c, dr, dc)
-            .and_then(|(rr, cc)| self.rows.get(rr)?.get(cc))
+        self.rows
+            .offset((r, c), (dr, dc))
+            .and_then(|pos| self.rows.get(pos))
     }
 
     fn is_convex_corner(&self, r: usize, c: usize) -> usize {
-        let tile = &self.rows[r][c];
+        let tile = &self.rows[(r, c)];
         [
             [(0, -1), (-1, -1), (-1, 0)],
             [(-1, 0), (-1, 1), (0, 1)],
@@ -63,7 +61,7 @@ impl Map {
     }
 
     fn is_after_concave_corner(&self, r: usize, c: usize) -> usize {
-        let tile = &self.rows[r][c];
+        let tile = &self.rows[(r, c)];
         [
             ((-1, -1), (-1, 0)),
             ((-1, 1), (0, 1)),
@@ -84,10 +82,8 @@ impl Map {
     }
 }
 
-fn chart(mut rows: Vec<Vec<Tile>>) -> Map {
+fn chart(mut rows: Grid<Tile>) -> Map {
     let mut regions = Vec::new();
-    let h = rows.len();
-    let w = rows[0].len();
 
     let mut region_q = VecDeque::new();
     let mut perimeter_q = VecDeque::new();
@@ -95,28 +91,21 @@ fn chart(mut rows: Vec<Vec<Tile>>) -> Map {
     perimeter_q.push_back((0, 0));
     while let Some((r, c)) = perimeter_q.pop_front() {
         if !visited.contains(&(r, c)) {
-            let region_plant = rows[r][c].plant;
+            let region_plant = rows[(r, c)].plant;
             let mut region = HashSet::new();
             region_q.push_back((r, c));
 
             while let Some((r, c)) = region_q.pop_front() {
                 if !visited.contains(&(r, c)) {
-                    if rows[r][c].plant == region_plant {
+                    if rows[(r, c)].plant == region_plant {
                         visited.insert((r, c));
                         region.insert((r, c));
 
-                        for (rr, cc) in [
-                            r.checked_sub(1).map(|rr| (rr, c)),
-                            c.checked_sub(1).map(|cc| (r, cc)),
-                            Some((r + 1, c)),
-                            Some((r, c + 1)),
-                        ]
-                        .iter()
-                        .flatten()
-                        .copied()
-                        .filter(|(rr, cc)| (0..h).contains(rr) && (0..w).contains(cc))
-                        {
-                            let neighbor = &mut rows[rr][cc];
+                        for dir in Dir4::ALL {
+                            let Some((rr, cc)) = rows.offset((r, c), dir) else {
+                                continue;
+                            };
+                            let neighbor = &mut rows[(rr, cc)];
                             if neighbor.plant == region_plant {
                                 neighbor.neighbors += 1;
                             }
@@ -144,7 +133,7 @@ fn solve_a(map: &Map) -> usize {
                 * (4 * tiles.len()
                     - tiles
                         .iter()
-                        .map(|(r, c)| map.rows[*r][*c].neighbors)
+                        .map(|pos| map.rows[*pos].neighbors)
                         .sum::<usize>())
         })
         .sum()
@@ -158,7 +147,7 @@ fn solve_b(map: &Map) -> usize {
                 * tiles
                     .iter()
                     .copied()
-                    .map(|(r, c)| match map.rows[r][c].neighbors {
+                    .map(|(r, c)| match map.rows[(r, c)].neighbors {
                         0 => 4,
                         1..=3 => map.is_convex_corner(r, c) + map.is_after_concave_corner(r, c),
                         4 => 0,
@@ -169,21 +158,50 @@ fn solve_b(map: &Map) -> usize {
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let map = chart(
-        lines
-            .iter()
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                line.chars()
-                    .map(|plant| Tile {
-                        plant,
-                        neighbors: 0,
-                    })
-                    .collect()
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+",
+    part_a: Some("1930"),
+    part_b: Some("1206"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Map;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let rows = input.grid("plant", |plant| {
+            Some(Tile {
+                plant,
+                neighbors: 0,
             })
-            .collect(),
-    );
+        })?;
+        if rows.is_empty() {
+            return Err(ParseError::end_of_input(input, "garden map"));
+        }
+        Ok(chart(rows))
+    }
 
-    (solve_a(&map).to_string(), solve_b(&map).to_string())
+    fn part_a(&self, map: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(map).to_string()
+    }
+
+    fn part_b(&self, map: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(map).to_string()
+    }
 }
@@ -14,12 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::{Block, Input},
+    solver::{Example, Params, Solver},
+};
 
 #[cfg(debug_assertions)]
 use crate::util::gcd;
 
-struct Game {
+pub struct Game {
     a: (i64, i64),
     b: (i64, i64),
     prize: (i64, i64),
@@ -59,56 +63,100 @@ fn solve_a(games: &[Game]) -> i64 {
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    fn parse_line(prefix: &str, op: &str, line: &str) -> Option<(i64, i64)> {
-        let (xs, ys) = line
-            .trim()
-            .strip_prefix(prefix)?
-            .strip_prefix(": X")?
-            .strip_prefix(op)?
-            .split_once(',')?;
-        Some((
-            xs.parse().ok()?,
-            ys.strip_prefix(" Y")?.strip_prefix(op)?.parse().ok()?,
-        ))
-    }
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+",
+    part_a: Some("480"),
+    part_b: None,
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Vec<Game>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        fn parse_line(prefix: &str, op: &str, line: &str) -> Option<(i64, i64)> {
+            let (xs, ys) = line
+                .trim()
+                .strip_prefix(prefix)?
+                .strip_prefix(": X")?
+                .strip_prefix(op)?
+                .split_once(',')?;
+            Some((
+                xs.parse().ok()?,
+                ys.strip_prefix(" Y")?.strip_prefix(op)?.parse().ok()?,
+            ))
+        }
 
-    let (games, _, _) = lines.iter().filter(|line| !line.is_empty()).fold(
-        (Vec::with_capacity((lines.len() + 1) / 4), None, None),
-        |(mut games, a, b), line| match (a, b) {
-            (None, None) => (
-                games,
-                Some(parse_line("Button A", "+", line).unwrap()),
-                None,
-            ),
-            (Some(a), None) => (
-                games,
-                Some(a),
-                Some(parse_line("Button B", "+", line).unwrap()),
-            ),
-            (Some(a), Some(b)) => {
-                games.push(Game {
-                    a,
-                    b,
-                    prize: parse_line("Prize", "=", line).unwrap(),
-                });
-                (games, None, None)
+        fn expect_line(
+            block: &Block,
+            line: Option<(usize, &str)>,
+            prefix: &str,
+            op: &str,
+        ) -> Result<(i64, i64), ParseError> {
+            let expected = || format!("\"{prefix}: X{op}<int>, Y{op}<int>\"");
+            match line {
+                Some((line_num, line)) => parse_line(prefix, op, line)
+                    .ok_or_else(|| ParseError::at(line_num, line, line, expected())),
+                None => Err(ParseError::end_of_block(block, expected())),
             }
-            _ => unreachable!(),
-        },
-    );
+        }
 
-    (
-        solve_a(&games).to_string(),
+        let games = input
+            .blocks()
+            .map(|block| {
+                let mut lines = block.numbered_lines();
+                let game = Game {
+                    a: expect_line(&block, lines.next(), "Button A", "+")?,
+                    b: expect_line(&block, lines.next(), "Button B", "+")?,
+                    prize: expect_line(&block, lines.next(), "Prize", "=")?,
+                };
+                match lines.next() {
+                    Some((line_num, line)) => {
+                        Err(ParseError::at(line_num, line, line, "empty line"))
+                    }
+                    None => Ok(game),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(games)
+    }
+
+    fn part_a(&self, games: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(games).to_string()
+    }
+
+    fn part_b(&self, games: &Self::Parsed<'_>, _params: &Params) -> String {
         solve_a(
             &games
-                .into_iter()
+                .iter()
                 .map(|game| Game {
                     prize: (game.prize.0 + 10000000000000, game.prize.1 + 10000000000000),
-                    ..game
+                    ..*game
                 })
                 .collect::<Vec<_>>(),
         )
-        .to_string(),
-    )
+        .to_string()
+    }
 }
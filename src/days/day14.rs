@@ -16,17 +16,19 @@
 
 use std::cmp::Ordering;
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Param, Params, Solver},
+};
 
-struct Robot {
+pub struct Robot {
     p: (i64, i64),
     v: (i64, i64),
 }
 
-const W: i64 = 101;
-const H: i64 = 103;
-
-fn solve_a(robots: &[Robot], steps: i64) -> i64 {
+fn solve_a(robots: &[Robot], steps: i64, w: i64, h: i64) -> i64 {
     let (q1, q2, q3, q4) = robots.iter().fold(
         (0, 0, 0, 0),
         |(q1, q2, q3, q4),
@@ -34,9 +36,9 @@ fn solve_a(robots: &[Robot], steps: i64) -> i64 {
              p: (px, py),
              v: (vx, vy),
          }| {
-            let x = (px + vx * steps).rem_euclid(W);
-            let y = (py + vy * steps).rem_euclid(H);
-            match (x.cmp(&(W / 2)), y.cmp(&(H / 2))) {
+            let x = (px + vx * steps).rem_euclid(w);
+            let y = (py + vy * steps).rem_euclid(h);
+            match (x.cmp(&(w / 2)), y.cmp(&(h / 2))) {
                 (Ordering::Equal, _) | (_, Ordering::Equal) => (q1, q2, q3, q4),
                 (Ordering::Greater, Ordering::Greater) => (q1 + 1, q2, q3, q4),
                 (Ordering::Less, Ordering::Greater) => (q1, q2 + 1, q3, q4),
@@ -53,7 +55,7 @@ fn dispersion(nums: &[i64]) -> u64 {
     nums.iter().copied().map(|a| a.abs_diff(mean)).sum()
 }
 
-fn solve_b(robots: &[Robot]) -> i64 {
+fn solve_b(robots: &[Robot], w: i64, h: i64) -> i64 {
     let mut last_dispersion_x = dispersion(
         &robots
             .iter()
@@ -67,6 +69,7 @@ fn solve_b(robots: &[Robot]) -> i64 {
             .collect::<Vec<_>>(),
     );
     for step in 1..100_000 {
+        cancel::check();
         let (xs, ys): (Vec<i64>, Vec<i64>) = robots.iter().fold(
             (
                 Vec::with_capacity(robots.len()),
@@ -77,8 +80,8 @@ fn solve_b(robots: &[Robot]) -> i64 {
                  p: (px, py),
                  v: (vx, vy),
              }| {
-                let x = (px + vx * step).rem_euclid(W);
-                let y = (py + vy * step).rem_euclid(H);
+                let x = (px + vx * step).rem_euclid(w);
+                let y = (py + vy * step).rem_euclid(h);
                 xs.push(x);
                 ys.push(y);
                 (xs, ys)
@@ -93,7 +96,7 @@ fn solve_b(robots: &[Robot]) -> i64 {
         {
             #[cfg(feature = "print")]
             {
-                let mut grid: Vec<Vec<u8>> = vec![vec![0; W as usize]; H as usize];
+                let mut grid: Vec<Vec<u8>> = vec![vec![0; w as usize]; h as usize];
                 for (x, y) in xs.iter().zip(ys.iter()) {
                     grid[*y as usize][*x as usize] += 1;
                 }
@@ -119,23 +122,91 @@ fn solve_b(robots: &[Robot]) -> i64 {
     unreachable!()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let robots = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (ps, vs) = line.split_once(' ').unwrap();
-            let (px, py) = ps.strip_prefix("p=").unwrap().split_once(',').unwrap();
-            let (vx, vy) = vs.strip_prefix("v=").unwrap().split_once(',').unwrap();
-            Robot {
-                p: (px.parse().unwrap(), py.parse().unwrap()),
-                v: (vx.parse().unwrap(), vy.parse().unwrap()),
-            }
-        })
-        .collect::<Vec<_>>();
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+",
+    part_a: Some("12"),
+    part_b: None,
+    params: &[("width", 11), ("height", 7)],
+}];
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "width",
+        default: 101,
+        min: 1,
+        description: "Width of the room",
+    },
+    Param {
+        name: "height",
+        default: 103,
+        min: 1,
+        description: "Height of the room",
+    },
+];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = Vec<Robot>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        fn parse_vector(
+            line_num: usize,
+            line: &str,
+            s: &str,
+            prefix: &str,
+        ) -> Result<(i64, i64), ParseError> {
+            let (x, y) = s
+                .strip_prefix(prefix)
+                .and_then(|xy| xy.split_once(','))
+                .ok_or_else(|| ParseError::at(line_num, line, s, format!("\"{prefix}<x>,<y>\"")))?;
+            Ok((
+                parse_token(line_num, line, Some(x), "integer")?,
+                parse_token(line_num, line, Some(y), "integer")?,
+            ))
+        }
 
-    (
-        solve_a(&robots, 100).to_string(),
-        solve_b(&robots).to_string(),
-    )
+        let robots = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_num, line)| {
+                let (ps, vs) = line
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "' '"))?;
+                Ok(Robot {
+                    p: parse_vector(line_num, line, ps, "p=")?,
+                    v: parse_vector(line_num, line, vs, "v=")?,
+                })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        if robots.is_empty() {
+            return Err(ParseError::end_of_input(input, "robot"));
+        }
+        Ok(robots)
+    }
+
+    fn part_a(&self, robots: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_a(robots, 100, params.get("width"), params.get("height")).to_string()
+    }
+
+    fn part_b(&self, robots: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_b(robots, params.get("width"), params.get("height")).to_string()
+    }
 }
@@ -16,24 +16,30 @@
 
 use std::collections::HashSet;
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+    util::{
+        geom::{Delta, Dir4},
+        grid::Grid,
+    },
+};
 
 #[cfg(feature = "print")]
 fn print_state<const WIDE: bool>(
-    walls: &[Vec<bool>],
+    walls: &Grid<bool>,
     boxes: &HashSet<(usize, usize)>,
     pos: (usize, usize),
     step: usize,
     num_steps: usize,
-    dir: u8,
+    dir: Dir4,
 ) {
     println!("Step {} of {}", step, num_steps);
     println!(
         "    {}",
-        walls[0]
-            .iter()
-            .enumerate()
-            .map(|(i, _)| if i % 10 == 0 {
+        (0..walls.width())
+            .map(|i| if i % 10 == 0 {
                 (i / 10).to_string()
             } else {
                 " ".to_string()
@@ -42,19 +48,17 @@ fn print_state<const WIDE: bool>(
     );
     println!(
         "    {}",
-        walls[0]
-            .iter()
-            .enumerate()
-            .map(|(i, _)| (i % 10).to_string())
+        (0..walls.width())
+            .map(|i| (i % 10).to_string())
             .collect::<String>()
     );
-    for (r, row) in walls.iter().enumerate() {
+    for r in 0..walls.height() {
         println!(
             "{:02}  {}",
             r,
-            (0..row.len())
+            (0..walls.width())
                 .map(|c| {
-                    if walls[r][c] {
+                    if walls[(r, c)] {
                         '#'
                     } else if boxes.contains(&(r, c)) {
                         if WIDE {
@@ -65,13 +69,7 @@ fn print_state<const WIDE: bool>(
                     } else if WIDE && boxes.contains(&(r, c - 1)) {
                         ']'
                     } else if pos == (r, c) {
-                        match dir {
-                            0 => '^',
-                            1 => '>',
-                            2 => 'v',
-                            3 => '<',
-                            _ => unreachable!(),
-                        }
+                        dir.to_char()
                     } else {
                         '.'
                     }
@@ -88,7 +86,7 @@ fn collect_moving_boxes<const WIDE: bool>(
     dr: isize,
     dc: isize,
     boxes: &HashSet<(usize, usize)>,
-    walls: &[Vec<bool>],
+    walls: &Grid<bool>,
     mut moving: Vec<(usize, usize)>,
 ) -> Option<Vec<(usize, usize)>> {
     if let Some((br, bc)) = boxes
@@ -100,7 +98,7 @@ fn collect_moving_boxes<const WIDE: bool>(
         let brr = br.wrapping_add_signed(dr);
         let bcc = bc.wrapping_add_signed(dc);
         let bcc_neighbor = bc.wrapping_add_signed(if WIDE && dc > 0 { 2 * dc } else { dc });
-        if !(walls[brr][bcc] || (WIDE && walls[brr][bcc + 1])) {
+        if !(walls[(brr, bcc)] || (WIDE && walls[(brr, bcc + 1)])) {
             let moving = if WIDE && dr != 0 {
                 collect_moving_boxes::<WIDE>(brr, bcc_neighbor + 1, dr, dc, boxes, walls, moving)
             } else {
@@ -115,20 +113,26 @@ fn collect_moving_boxes<const WIDE: bool>(
     }
 }
 
+type Warehouse = (
+    Grid<bool>,
+    HashSet<(usize, usize)>,
+    (usize, usize),
+    Vec<Dir4>,
+);
+
 fn simulate<const WIDE: bool>(
-    walls: Vec<Vec<bool>>,
+    walls: Grid<bool>,
     boxes: HashSet<(usize, usize)>,
     start: (usize, usize),
-    moves: &[u8],
-) -> (Vec<Vec<bool>>, usize) {
+    moves: &[Dir4],
+) -> (Grid<bool>, usize) {
     let (mut r, c) = start;
     let (mut c, walls, mut boxes) = if WIDE {
         (
             c * 2,
-            walls
-                .into_iter()
-                .map(|row| row.into_iter().flat_map(|cell| [cell, cell]).collect())
-                .collect(),
+            Grid::from_fn(walls.width() * 2, walls.height(), |(r, c)| {
+                walls[(r, c / 2)]
+            }),
             boxes.into_iter().map(|(r, c)| (r, c * 2)).collect(),
         )
     } else {
@@ -139,16 +143,10 @@ fn simulate<const WIDE: bool>(
     let mut i = 0;
 
     for dir in moves.iter() {
-        let (dr, dc): (isize, isize) = match dir {
-            0 => (-1, 0),
-            1 => (0, 1),
-            2 => (1, 0),
-            3 => (0, -1),
-            _ => unreachable!(),
-        };
+        let Delta { dr, dc } = dir.delta();
         let rr = r.checked_add_signed(dr).unwrap();
         let cc = c.checked_add_signed(dc).unwrap();
-        if !walls[rr][cc] {
+        if !walls[(rr, cc)] {
             if let Some(boxes_moving) =
                 collect_moving_boxes::<WIDE>(rr, cc, dr, dc, &boxes, &walls, Vec::new())
             {
@@ -180,57 +178,85 @@ fn simulate<const WIDE: bool>(
         (r, c),
         moves.len(),
         moves.len(),
-        moves.last().copied().unwrap_or(0),
+        moves.last().copied().unwrap_or(Dir4::Up),
     );
 
     (walls, boxes.iter().map(|(r, c)| r * 100 + c).sum())
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (walls, boxes, start) = lines
-        .iter()
-        .skip_while(|line| line.is_empty())
-        .take_while(|line| !line.is_empty())
-        .enumerate()
-        .fold(
-            (Vec::new(), HashSet::new(), (0, 0)),
-            |(mut walls, boxes, start), (r, line)| {
-                let (row, boxes, start) = line.chars().enumerate().fold(
-                    (Vec::with_capacity(line.len()), boxes, start),
-                    |(mut row, mut boxes, mut start), (c, ch)| {
-                        row.push(ch == '#');
-                        match ch {
-                            'O' => {
-                                boxes.insert((r, c));
-                            }
-                            '@' => start = (r, c),
-                            _ => {}
-                        };
-                        (row, boxes, start)
-                    },
-                );
-                walls.push(row);
-                (walls, boxes, start)
-            },
-        );
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
 
-    let moves: Vec<u8> = lines
-        .iter()
-        .skip_while(|line| line.is_empty())
-        .skip_while(|line| !line.is_empty())
-        .filter(|line| !line.is_empty())
-        .flat_map(|line| line.chars())
-        .map(|ch| match ch {
-            '^' => 0,
-            '>' => 1,
-            'v' => 2,
-            '<' => 3,
-            _ => unreachable!(),
-        })
-        .collect();
-
-    let (walls, solution_a) = simulate::<false>(walls, boxes.clone(), start, &moves);
-    let (_, solution_b) = simulate::<true>(walls, boxes, start, &moves);
-
-    (solution_a.to_string(), solution_b.to_string())
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+",
+    part_a: Some("10092"),
+    part_b: Some("9021"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Warehouse;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let mut blocks = input.blocks();
+        let map = Grid::parse(
+            blocks
+                .next()
+                .into_iter()
+                .flat_map(|block| block.numbered_lines()),
+            "'#', '.', 'O' or '@'",
+            |ch| Some(ch).filter(|ch| "#.O@".contains(*ch)),
+        )?;
+        let start = map
+            .find(&'@')
+            .ok_or_else(|| ParseError::end_of_input(input, "robot '@'"))?;
+        let boxes = map.find_all(&'O').collect();
+        let walls = map.map(|ch| *ch == '#');
+
+        let moves: Vec<Dir4> = blocks
+            .flat_map(|block| block.numbered_lines())
+            .flat_map(|(line_num, line)| {
+                line.chars().enumerate().map(move |(c, ch)| {
+                    Dir4::from_char(ch).ok_or_else(|| {
+                        ParseError::new(line_num, c + 1, "'^', '>', 'v' or '<'", format!("{ch:?}"))
+                    })
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((walls, boxes, start, moves))
+    }
+
+    fn part_a(&self, (walls, boxes, start, moves): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (_, solution_a) = simulate::<false>(walls.clone(), boxes.clone(), *start, moves);
+        solution_a.to_string()
+    }
+
+    fn part_b(&self, (walls, boxes, start, moves): &Self::Parsed<'_>, _params: &Params) -> String {
+        let (_, solution_b) = simulate::<true>(walls.clone(), boxes.clone(), *start, moves);
+        solution_b.to_string()
+    }
 }
@@ -14,120 +14,134 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashSet, rc::Rc};
+use std::collections::HashSet;
 
 use crate::{
-    common::Solution,
-    search::astar::{self, astar_all_best},
+    common::ParseError,
+    input::Input,
+    search::astar::{self, astar, astar_all_best},
+    solver::{Example, Params, Solver},
+    util::{
+        geom::{Dir4, Pos},
+        grid::Grid,
+    },
 };
 
 #[derive(Eq, PartialEq)]
-struct Game {
-    walls: Vec<Vec<bool>>,
-    start: (usize, usize),
-    end: (usize, usize),
+pub struct Game {
+    walls: Grid<bool>,
+    start: Pos,
+    end: Pos,
+}
+
+impl Game {
+    fn initial_state(&self) -> State<'_> {
+        State {
+            game: self,
+            pos: self.start,
+            dir: Dir4::Right,
+            score: 0,
+        }
+    }
+
+    fn is_wall(&self, pos: Pos) -> bool {
+        self.walls.get(pos).copied().unwrap_or(true)
+    }
 }
 
 #[derive(Eq, PartialEq)]
 struct State<'game> {
     game: &'game Game,
-    prev: Option<Rc<Self>>,
-    pos: (usize, usize),
-    dir: u8,
+    pos: Pos,
+    dir: Dir4,
     score: usize,
 }
 
 impl<'game> State<'game> {
-    fn step(&self) -> (usize, usize) {
-        let (r, c) = self.pos;
-        match self.dir {
-            0 => (r - 1, c),
-            1 => (r, c + 1),
-            2 => (r + 1, c),
-            3 => (r, c - 1),
-            _ => unreachable!(),
-        }
+    fn is_open(&self, dir: Dir4) -> bool {
+        !self.game.is_wall(self.pos.wrapping_add(dir.delta()))
     }
 
-    fn step_turn(&self, turn: u8) -> ((usize, usize), u8) {
-        let (r, c) = self.pos;
-        match (self.dir + turn) % 4 {
-            0 => ((r - 1, c), 0),
-            1 => ((r, c + 1), 1),
-            2 => ((r + 1, c), 2),
-            3 => ((r, c - 1), 3),
-            _ => unreachable!(),
-        }
+    fn is_corridor(&self) -> bool {
+        !self.is_open(self.dir.turn_left()) && !self.is_open(self.dir.turn_right())
     }
 
-    fn is_corridor(&self) -> bool {
-        let (r, c) = self.pos;
-        match self.dir {
-            0 | 2 => self.game.walls[r][c - 1] && self.game.walls[r][c + 1],
-            1 | 3 => self.game.walls[r - 1][c] && self.game.walls[r + 1][c],
-            _ => unreachable!(),
-        }
+    fn steps(self) -> impl Iterator<Item = Self> + 'game {
+        [
+            Self {
+                dir: self.dir.turn_right(),
+                score: self.score + 1000,
+                ..self
+            },
+            Self {
+                dir: self.dir.turn_left(),
+                score: self.score + 1000,
+                ..self
+            },
+            Self {
+                pos: self.pos.wrapping_add(self.dir.delta()),
+                score: self.score + 1,
+                ..self
+            },
+        ]
+        .into_iter()
+        .filter(|state| !state.game.is_wall(state.pos))
+    }
+
+    fn tiles_to(self, to: &(Pos, Dir4)) -> Vec<Pos> {
+        // Only the cheapest of the walks from here to `to` can be on a best path
+        let segments: Vec<(usize, Vec<Pos>)> = self
+            .steps()
+            .filter_map(|step| {
+                let mut tiles = Vec::new();
+                let end = step.walk(&mut |pos| tiles.push(pos));
+                ((end.pos, end.dir) == *to).then_some((end.score, tiles))
+            })
+            .collect();
+        let min = segments.iter().map(|(score, _)| *score).min();
+        segments
+            .into_iter()
+            .filter(|(score, _)| Some(*score) == min)
+            .flat_map(|(_, tiles)| tiles)
+            .collect()
     }
 
-    fn walk(self) -> Self {
+    fn walk(self, visit: &mut impl FnMut(Pos)) -> Self {
+        visit(self.pos);
         if self.pos == self.game.end {
             self
-        } else {
-            let forward = self.step();
-            let (fr, fc) = forward;
-            if let Some(pos) =
-                Some(forward).filter(|(rr, cc)| !self.game.walls[*rr][*cc] && self.is_corridor())
-            {
+        } else if self.is_open(self.dir) {
+            if self.is_corridor() {
                 Self {
-                    pos,
-                    dir: self.dir,
-                    game: self.game,
+                    pos: self.pos.wrapping_add(self.dir.delta()),
                     score: self.score + 1,
-                    prev: Some(Rc::new(self)),
-                }
-                .walk()
-            } else if let Some((pos, dir)) = {
-                let ((rr, rc), rdir) = self.step_turn(1);
-                let ((lr, lc), ldir) = self.step_turn(3);
-                if self.game.walls[fr][fc] {
-                    if self.game.walls[lr][lc] && !self.game.walls[rr][rc] {
-                        Some(((rr, rc), rdir))
-                    } else if self.game.walls[rr][rc] && !self.game.walls[lr][lc] {
-                        Some(((lr, lc), ldir))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } {
-                Self {
-                    pos,
-                    dir,
-                    game: self.game,
-                    score: self.score + 1001,
-                    prev: Some(Rc::new(self)),
+                    ..self
                 }
-                .walk()
+                .walk(visit)
             } else {
                 self
             }
-        }
-    }
-
-    fn path(&self, path: HashSet<(usize, usize)>) -> HashSet<(usize, usize)> {
-        let mut path = if let Some(prev) = &self.prev {
-            prev.path(path)
         } else {
-            path
-        };
-        path.insert(self.pos);
-        path
+            let right = self.dir.turn_right();
+            let left = self.dir.turn_left();
+            let dir = match (self.is_open(right), self.is_open(left)) {
+                (true, false) => right,
+                (false, true) => left,
+                _ => return self,
+            };
+            Self {
+                pos: self.pos.wrapping_add(dir.delta()),
+                dir,
+                score: self.score + 1001,
+                ..self
+            }
+            .walk(visit)
+        }
     }
 }
 
 impl<'game> astar::State for State<'game> {
-    type DuplicationKey = ((usize, usize), u8);
+    type DuplicationKey = (Pos, Dir4);
     type Value = usize;
     type NewStates = Box<dyn Iterator<Item = Self> + 'game>;
 
@@ -136,9 +150,7 @@ impl<'game> astar::State for State<'game> {
     }
 
     fn estimate(&self) -> Self::Value {
-        let (er, ec) = self.game.end;
-        let (r, c) = self.pos;
-        self.value() + r.abs_diff(er) + c.abs_diff(ec)
+        self.value() + self.pos.manhattan(self.game.end)
     }
 
     fn duplication_key(&self) -> Self::DuplicationKey {
@@ -146,76 +158,106 @@ impl<'game> astar::State for State<'game> {
     }
 
     fn generate_moves(self) -> Self::NewStates {
-        let prev = Rc::new(self);
-        Box::new(
-            [
-                Self {
-                    dir: (prev.dir + 1) % 4,
-                    score: prev.score + 1000,
-                    prev: Some(Rc::clone(&prev)),
-                    ..*prev
-                },
-                Self {
-                    dir: (prev.dir + 3) % 4,
-                    score: prev.score + 1000,
-                    prev: Some(Rc::clone(&prev)),
-                    ..*prev
-                },
-                Self {
-                    game: prev.game,
-                    pos: prev.step(),
-                    score: prev.score + 1,
-                    dir: prev.dir,
-                    prev: Some(prev),
-                },
-            ]
-            .into_iter()
-            .filter(|state| {
-                let (rr, cc) = state.pos;
-                !state.game.walls[rr][cc]
-            })
-            .map(State::walk),
-        )
+        Box::new(self.steps().map(|state| state.walk(&mut |_| {})))
     }
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (walls, start, end) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .fold(
-            (Vec::new(), (0, 0), (0, 0)),
-            |(mut walls, start, end), (r, line)| {
-                let (row, start, end) = line.chars().enumerate().fold(
-                    (Vec::with_capacity(line.len()), start, end),
-                    |(mut row, start, end), (c, ch)| {
-                        row.push(ch == '#');
-                        match ch {
-                            'S' => (row, (r, c), end),
-                            'E' => (row, start, (r, c)),
-                            _ => (row, start, end),
-                        }
-                    },
-                );
-                walls.push(row);
-                (walls, start, end)
-            },
-        );
-    let game = Game { walls, start, end };
-
-    let paths = astar_all_best(State {
-        game: &game,
-        prev: None,
-        pos: game.start,
-        dir: 1,
-        score: 0,
-    });
-    let solution_a = paths[0].score;
-    let tiles = paths
-        .into_iter()
-        .fold(HashSet::new(), |tiles, state| state.path(tiles));
-    let solution_b = tiles.len();
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+",
+        part_a: Some("7036"),
+        part_b: Some("45"),
+        params: &[],
+    },
+    Example {
+        input: "
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+",
+        part_a: Some("11048"),
+        part_b: Some("64"),
+        params: &[],
+    },
+];
 
-    (solution_a.to_string(), solution_b.to_string())
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Game;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let map = input.grid("'#', '.', 'S' or 'E'", |ch| {
+            Some(ch).filter(|ch| "#.SE".contains(*ch))
+        })?;
+        let start = map
+            .find(&'S')
+            .ok_or_else(|| ParseError::end_of_input(input, "start tile 'S'"))?
+            .into();
+        let end = map
+            .find(&'E')
+            .ok_or_else(|| ParseError::end_of_input(input, "end tile 'E'"))?
+            .into();
+        let walls = map.map(|ch| *ch == '#');
+        Ok(Game { walls, start, end })
+    }
+
+    fn part_a(&self, game: &Self::Parsed<'_>, _params: &Params) -> String {
+        astar(game.initial_state())
+            .map(|result| result.state.score.to_string())
+            .unwrap_or_else(|| "no path".to_string())
+    }
+
+    fn part_b(&self, game: &Self::Parsed<'_>, _params: &Params) -> String {
+        let paths = astar_all_best(game.initial_state());
+        if paths.goals.is_empty() {
+            return "no path".to_string();
+        }
+        let mut tiles = HashSet::from([game.start]);
+        for key in paths.keys() {
+            for &(pos, dir) in &paths.predecessors[key] {
+                let pred = State {
+                    game,
+                    pos,
+                    dir,
+                    score: 0,
+                };
+                tiles.extend(pred.tiles_to(key));
+            }
+        }
+        tiles.len().to_string()
+    }
 }
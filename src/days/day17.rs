@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::{parse_token, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 const ADV: u8 = 0;
 const BXL: u8 = 1;
@@ -113,6 +118,7 @@ fn solve_b(program: &[u8], b: u64, c: u64, find_output: &[u8]) -> u64 {
     let mut bit_segments = vec![0; find_output.len()];
     let mut i = find_output.len() - 1;
     loop {
+        cancel::check();
         let a = bit_segments
             .iter()
             .rev()
@@ -136,41 +142,98 @@ fn solve_b(program: &[u8], b: u64, c: u64, find_output: &[u8]) -> u64 {
     }
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (a, b, c) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map_while(|line| line.strip_prefix("Register"))
-        .map(|line| line.trim())
-        .fold((0, 0, 0), |(a, b, c), line| {
-            if let Some(a) = line.strip_prefix("A:") {
-                (a.trim().parse().unwrap(), b, c)
-            } else if let Some(b) = line.strip_prefix("B:") {
-                (a, b.trim().parse().unwrap(), c)
-            } else if let Some(c) = line.strip_prefix("C:") {
-                (a, b, c.trim().parse().unwrap())
-            } else {
-                unreachable!()
-            }
-        });
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "
+Register A: 729
+Register B: 0
+Register C: 0
 
-    let program: Vec<u8> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .skip_while(|line| line.starts_with("Register"))
-        .flat_map(|line| line.strip_prefix("Program:"))
-        .flat_map(|line| line.trim().split(','))
-        .map(|s| s.parse().unwrap())
-        .collect();
-
-    let solution_a = solve_a(&program, a, b, c);
-    let solution_b = solve_b(&program, b, c, &program);
-    (
-        solution_a
+Program: 0,1,5,4,3,0
+",
+        part_a: Some("4,6,3,5,6,3,5,2,1,0"),
+        part_b: None,
+        params: &[],
+    },
+    Example {
+        input: "
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+",
+        part_a: None,
+        part_b: Some("117440"),
+        params: &[],
+    },
+];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (u64, u64, u64, Vec<u8>);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let (a, b, c) = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map_while(|(line_num, line)| Some((line_num, line, line.strip_prefix("Register")?)))
+            .map(|(line_num, line, reg)| (line_num, line, reg.trim()))
+            .try_fold((0, 0, 0), |(a, b, c), (line_num, line, reg)| {
+                let value = |v: &str| parse_token(line_num, line, Some(v.trim()), "register value");
+                if let Some(a) = reg.strip_prefix("A:") {
+                    Ok((value(a)?, b, c))
+                } else if let Some(b) = reg.strip_prefix("B:") {
+                    Ok((a, value(b)?, c))
+                } else if let Some(c) = reg.strip_prefix("C:") {
+                    Ok((a, b, value(c)?))
+                } else {
+                    Err(ParseError::at(
+                        line_num,
+                        line,
+                        reg,
+                        "\"A:\", \"B:\" or \"C:\"",
+                    ))
+                }
+            })?;
+
+        let program: Vec<u8> = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .skip_while(|(_, line)| line.starts_with("Register"))
+            .flat_map(|(line_num, line)| {
+                line.strip_prefix("Program:")
+                    .map(|program| program.trim().split(','))
+                    .into_iter()
+                    .flatten()
+                    .map(move |s| match s.parse() {
+                        Ok(i) if i < 8 => Ok(i),
+                        _ => Err(ParseError::at(line_num, line, s, "3-bit number")),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        if program.is_empty() || !program.len().is_multiple_of(2) {
+            return Err(ParseError::end_of_input(
+                input,
+                "program of instruction-operand pairs",
+            ));
+        }
+        Ok((a, b, c, program))
+    }
+
+    fn part_a(&self, (a, b, c, program): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(program, *a, *b, *c)
             .into_iter()
             .map(|i| i.to_string())
             .collect::<Vec<String>>()
-            .join(","),
-        solution_b.to_string(),
-    )
+            .join(",")
+    }
+
+    fn part_b(&self, (_, b, c, program): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(program, *b, *c, program).to_string()
+    }
 }
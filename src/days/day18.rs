@@ -14,157 +14,138 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::{HashMap, HashSet};
-
 use crate::{
-    common::Solution,
-    search::astar::{self, astar, State as AstarState},
+    common::{parse_token, ParseError},
+    input::Input,
+    search::graph::bfs_by,
+    solver::{Example, Param, Params, Solver},
+    util::grid::Grid,
 };
 
-const GOAL: (usize, usize) = (70, 70);
-const T: usize = 1024;
-
-#[derive(Eq, PartialEq)]
-struct Game<'walls> {
-    walls: &'walls HashMap<(usize, usize), usize>,
-    start: (usize, usize),
-    end: (usize, usize),
-    t: usize,
-}
-
-#[derive(Eq, PartialEq)]
-struct State<'game> {
-    game: &'game Game<'game>,
-    pos: (usize, usize),
-    steps: usize,
-}
-
-impl<'game> astar::State for State<'game> {
-    type DuplicationKey = (usize, usize);
-    type Value = usize;
-    type NewStates = Box<dyn Iterator<Item = Self> + 'game>;
-
-    fn value(&self) -> Self::Value {
-        self.steps
-    }
-
-    fn estimate(&self) -> Self::Value {
-        let (er, ec) = self.game.end;
-        let (r, c) = self.pos;
-        self.value() + r.abs_diff(er) + c.abs_diff(ec)
-    }
-
-    fn duplication_key(&self) -> Self::DuplicationKey {
-        self.pos
-    }
-
-    fn generate_moves(self) -> Self::NewStates {
-        let (r, c) = self.pos;
-        Box::new(
-            [
-                Some((r + 1, c)),
-                Some((r, c + 1)),
-                r.checked_sub(1).map(|rr| (rr, c)),
-                c.checked_sub(1).map(|cc| (r, cc)),
-            ]
-            .into_iter()
-            .flatten()
-            .filter(|pos| {
-                let (rr, cc) = *pos;
-                let (er, ec) = self.game.end;
-                rr <= er
-                    && cc <= ec
-                    && self
-                        .game
-                        .walls
-                        .get(pos)
-                        .map(|wt| *wt >= self.game.t)
-                        .unwrap_or(true)
-            })
-            .map(move |pos| State {
-                pos,
-                steps: self.steps + 1,
-                ..self
-            }),
-        )
+fn fall_times(bytes: &[(usize, usize)], size: usize) -> Grid<Option<usize>> {
+    let mut walls = Grid::filled(size + 1, size + 1, None);
+    for (t, pos) in bytes.iter().enumerate() {
+        if let Some(wall) = walls.get_mut(*pos) {
+            wall.get_or_insert(t);
+        }
     }
+    walls
 }
 
-fn is_solvable(game: &Game, state: State, visited: &mut HashSet<(usize, usize)>) -> bool {
-    let State { pos, .. } = state;
-    if pos == game.end {
-        true
-    } else if !visited.contains(&pos) {
-        visited.insert(pos);
-        state
-            .generate_moves()
-            .any(|state| is_solvable(game, state, visited))
-    } else {
-        false
-    }
+fn shortest_path(walls: &Grid<Option<usize>>, t: usize) -> Option<usize> {
+    let end = (walls.height() - 1, walls.width() - 1);
+    bfs_by(
+        (0, 0),
+        |pos| {
+            walls
+                .neighbors4(*pos)
+                .filter(|next| walls[*next].map(|wt| wt >= t).unwrap_or(true))
+        },
+        |pos| *pos == end,
+    )
+    .map(|(steps, _)| steps)
 }
 
-fn solve_b(walls: &HashMap<(usize, usize), usize>) -> (usize, usize) {
-    let mut t_min = T;
-    let mut t_max = walls.len();
+fn solve_b(bytes: &[(usize, usize)], size: usize, t_min: usize) -> (usize, usize) {
+    let walls = &fall_times(bytes, size);
+    let mut t_min = t_min;
+    let mut t_max = bytes.len();
     let tb = loop {
         if t_min == t_max {
             break t_min;
         }
         let t = (t_min + t_max) / 2;
-        let game = Game {
-            walls,
-            start: (0, 0),
-            end: GOAL,
-            t: t + 1,
-        };
-        let mut visited = HashSet::new();
-        if is_solvable(
-            &game,
-            State {
-                game: &game,
-                pos: game.start,
-                steps: 0,
-            },
-            &mut visited,
-        ) {
+        if shortest_path(walls, t + 1).is_some() {
             t_min = t + 1;
         } else {
             t_max = t;
         }
     };
-    *walls
-        .iter()
-        .find_map(|(pos, t)| if *t == tb { Some(pos) } else { None })
-        .unwrap()
+    bytes[tb]
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let walls = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .map(|(t, line)| {
-            let (xs, ys) = line.split_once(',').unwrap();
-            ((xs.parse().unwrap(), ys.parse().unwrap()), t)
-        })
-        .collect();
-    let game = Game {
-        walls: &walls,
-        start: (0, 0),
-        end: GOAL,
-        t: T,
-    };
-
-    let solution_a = astar(State {
-        game: &game,
-        pos: game.start,
-        steps: 0,
-    })
-    .unwrap()
-    .steps;
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+",
+    part_a: Some("22"),
+    part_b: Some("6,1"),
+    params: &[("size", 6), ("bytes", 12)],
+}];
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "size",
+        default: 70,
+        min: 0,
+        description: "Largest coordinate of the memory space",
+    },
+    Param {
+        name: "bytes",
+        default: 1024,
+        min: 0,
+        description: "Number of fallen bytes in part A",
+    },
+];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "RAM Run";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = Vec<(usize, usize)>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_num, line)| {
+                let (xs, ys) = line
+                    .split_once(',')
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "','"))?;
+                Ok((
+                    parse_token(line_num, line, Some(xs), "X coordinate")?,
+                    parse_token(line_num, line, Some(ys), "Y coordinate")?,
+                ))
+            })
+            .collect()
+    }
 
-    let (bx, by) = solve_b(&walls);
+    fn part_a(&self, bytes: &Self::Parsed<'_>, params: &Params) -> String {
+        let walls = fall_times(bytes, params.get("size"));
+        shortest_path(&walls, params.get("bytes"))
+            .unwrap()
+            .to_string()
+    }
 
-    (solution_a.to_string(), format!("{},{}", bx, by))
+    fn part_b(&self, bytes: &Self::Parsed<'_>, params: &Params) -> String {
+        let (bx, by) = solve_b(bytes, params.get("size"), params.get("bytes"));
+        format!("{},{}", bx, by)
+    }
 }
@@ -16,7 +16,12 @@
 
 use std::collections::HashMap;
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 fn count_solutions<'pat>(
     patterns: &'pat [&'pat str],
@@ -34,26 +39,69 @@ fn count_solutions<'pat>(
     })
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let patterns: Vec<&str> = lines
-        .iter()
-        .skip_while(|line| line.is_empty())
-        .take_while(|line| !line.is_empty())
-        .flat_map(|line| line.split(','))
-        .map(|s| s.trim())
-        .collect();
-
+fn count_all<'pat>(patterns: &'pat [&'pat str], designs: &'pat [&'pat str]) -> Vec<usize> {
     let mut memo = HashMap::new();
     memo.insert("", 1);
-    let (solution_a, solution_b): (usize, usize) = lines
+    designs
         .iter()
-        .skip_while(|line| line.is_empty())
-        .skip_while(|line| !line.is_empty())
-        .filter(|line| !line.is_empty())
-        .fold((0, 0), |(a, b), goal| {
-            let sol = count_solutions(&patterns, goal, &mut memo);
-            (a + if sol > 0 { 1 } else { 0 }, b + sol)
-        });
-
-    (solution_a.to_string(), solution_b.to_string())
+        .map(|goal| {
+            cancel::check();
+            count_solutions(patterns, goal, &mut memo)
+        })
+        .collect()
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+",
+    part_a: Some("6"),
+    part_b: Some("16"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Linen Layout";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Vec<&'input str>, Vec<&'input str>);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let mut blocks = input.blocks();
+        let patterns: Vec<&str> = blocks
+            .next()
+            .into_iter()
+            .flat_map(|block| block.lines())
+            .flat_map(|line| line.split(','))
+            .map(|s| s.trim())
+            .collect();
+        let designs: Vec<&str> = blocks.flat_map(|block| block.lines()).collect();
+        Ok((patterns, designs))
+    }
+
+    fn part_a(&self, (patterns, designs): &Self::Parsed<'_>, _params: &Params) -> String {
+        count_all(patterns, designs)
+            .into_iter()
+            .filter(|sol| *sol > 0)
+            .count()
+            .to_string()
+    }
+
+    fn part_b(&self, (patterns, designs): &Self::Parsed<'_>, _params: &Params) -> String {
+        count_all(patterns, designs)
+            .into_iter()
+            .sum::<usize>()
+            .to_string()
+    }
 }
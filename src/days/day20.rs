@@ -16,11 +16,17 @@
 
 use std::collections::BTreeMap;
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::ParseError,
+    input::Input,
+    solver::{Example, Param, Params, Solver},
+    util::grid::Grid,
+};
 
 #[derive(Eq, PartialEq)]
-struct Game {
-    walls: Vec<Vec<bool>>,
+pub struct Game {
+    walls: Grid<bool>,
     start: (usize, usize),
     end: (usize, usize),
 }
@@ -31,19 +37,16 @@ fn navigate(game: &Game) -> Vec<(usize, usize)> {
     let walls = &game.walls;
     while states[states.len() - 1] != game.end {
         let pos = states[states.len() - 1];
-        let (r, c) = pos;
-        let next_pos = [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
-            .into_iter()
-            .find(|(rr, cc)| {
-                (1..walls.len() - 1).contains(rr)
-                    && (1..walls[0].len() - 1).contains(cc)
-                    && states
-                        .len()
-                        .checked_sub(2)
-                        .map(|i| states[i])
-                        .map(|prev_pos| (*rr, *cc) != prev_pos)
-                        .unwrap_or(true)
-                    && !walls[*rr][*cc]
+        let next_pos = walls
+            .neighbors4(pos)
+            .find(|next| {
+                states
+                    .len()
+                    .checked_sub(2)
+                    .map(|i| states[i])
+                    .map(|prev_pos| *next != prev_pos)
+                    .unwrap_or(true)
+                    && !walls[*next]
             })
             .unwrap();
         states.push(next_pos);
@@ -55,12 +58,13 @@ fn find_cheats(
     path: &[(usize, usize)],
     path_r: &BTreeMap<usize, BTreeMap<usize, usize>>,
     cheat_time: usize,
+    threshold: usize,
 ) -> usize {
-    const CHEAT_THRESHOLD: usize = 100;
     path.iter()
-        .take(path.len() - CHEAT_THRESHOLD + 1)
+        .take((path.len() + 1).saturating_sub(threshold))
         .enumerate()
         .map(|(ta, (ra, ca))| {
+            cancel::check();
             path_r
                 .range((ra.saturating_sub(cheat_time))..=(ra + cheat_time))
                 .map(move |(rb, path_c)| {
@@ -70,7 +74,7 @@ fn find_cheats(
                         .range(ca.saturating_sub(ct)..=(ca + ct))
                         .filter(move |(cb, tb)| {
                             let dist = dr + ca.abs_diff(**cb);
-                            tb.saturating_sub(ta + dist) >= CHEAT_THRESHOLD
+                            tb.saturating_sub(ta + dist) >= threshold
                         })
                         .count()
                 })
@@ -79,31 +83,8 @@ fn find_cheats(
         .sum()
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let (walls, start, end) = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .fold(
-            (Vec::new(), (0, 0), (0, 0)),
-            |(mut walls, start, end), (r, line)| {
-                let (row, start, end) = line.chars().enumerate().fold(
-                    (Vec::with_capacity(line.len()), start, end),
-                    |(mut row, start, end), (c, ch)| {
-                        row.push(ch == '#');
-                        match ch {
-                            'S' => (row, (r, c), end),
-                            'E' => (row, start, (r, c)),
-                            _ => (row, start, end),
-                        }
-                    },
-                );
-                walls.push(row);
-                (walls, start, end)
-            },
-        );
-    let game = Game { walls, start, end };
-    let path: Vec<(usize, usize)> = navigate(&game);
+fn count_cheats(game: &Game, cheat_time: usize, threshold: usize) -> usize {
+    let path: Vec<(usize, usize)> = navigate(game);
     let path_r: BTreeMap<usize, BTreeMap<usize, usize>> =
         path.iter()
             .enumerate()
@@ -111,9 +92,67 @@ pub fn solve(lines: &[String]) -> Solution {
                 path_r.entry(*r).or_default().insert(*c, t);
                 path_r
             });
+    find_cheats(&path, &path_r, cheat_time, threshold)
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+",
+    part_a: Some("1"),
+    part_b: Some("86"),
+    params: &[("threshold", 64)],
+}];
+
+const PARAMS: &[Param] = &[Param {
+    name: "threshold",
+    default: 100,
+    min: 1,
+    description: "Minimum number of picoseconds a cheat must save",
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Race Condition";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = Game;
 
-    (
-        find_cheats(&path, &path_r, 2).to_string(),
-        find_cheats(&path, &path_r, 20).to_string(),
-    )
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let map = input.grid("'#', '.', 'S' or 'E'", |ch| {
+            Some(ch).filter(|ch| "#.SE".contains(*ch))
+        })?;
+        let start = map
+            .find(&'S')
+            .ok_or_else(|| ParseError::end_of_input(input, "start tile 'S'"))?;
+        let end = map
+            .find(&'E')
+            .ok_or_else(|| ParseError::end_of_input(input, "end tile 'E'"))?;
+        let walls = map.map(|ch| *ch == '#');
+        Ok(Game { walls, start, end })
+    }
+
+    fn part_a(&self, game: &Self::Parsed<'_>, params: &Params) -> String {
+        count_cheats(game, 2, params.get("threshold")).to_string()
+    }
+
+    fn part_b(&self, game: &Self::Parsed<'_>, params: &Params) -> String {
+        count_cheats(game, 20, params.get("threshold")).to_string()
+    }
 }
@@ -16,7 +16,12 @@
 
 use std::collections::HashMap;
 
-use crate::{common::Solution, util::iter::WithSliding};
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Param, Params, Solver},
+    util::{geom::Dir4, iter::WithSliding},
+};
 
 const NUM_KEYPAD: &[(i8, i8)] = &[
     (2, 4),
@@ -32,11 +37,11 @@ const NUM_KEYPAD: &[(i8, i8)] = &[
     (3, 4),
 ];
 
-const DIR_KEYPAD: &[(i8, i8)] = &[(2, 1), (1, 2), (2, 2), (3, 2), (3, 1)];
-const UP: u8 = 0;
-const LEFT: u8 = 1;
-const DOWN: u8 = 2;
-const RIGHT: u8 = 3;
+const DIR_KEYPAD: &[(i8, i8)] = &[(2, 1), (3, 2), (2, 2), (1, 2), (3, 1)];
+
+fn dir_button(dir: Dir4) -> u8 {
+    dir.index() as u8
+}
 
 type Presses = HashMap<(u8, u8), usize>;
 
@@ -74,8 +79,8 @@ fn expand_presses(
                     let dy = ty - y;
 
                     let btn_a = (next_keypad.len() - 1) as u8;
-                    let btn_x = if dx >= 0 { RIGHT } else { LEFT };
-                    let btn_y = if dy >= 0 { DOWN } else { UP };
+                    let btn_x = dir_button(if dx >= 0 { Dir4::Right } else { Dir4::Left });
+                    let btn_y = dir_button(if dy >= 0 { Dir4::Down } else { Dir4::Up });
 
                     let x_first = if *prefer_x.get(&(dx, dy)).unwrap_or(&true) {
                         prev_keypad.contains(&(tx, y))
@@ -183,15 +188,70 @@ fn solve_ab(codes: &[&str], layers: usize) -> usize {
     }
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let codes: Vec<&str> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.trim())
-        .collect();
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+029A
+980A
+179A
+456A
+379A
+",
+    part_a: Some("126384"),
+    part_b: None,
+    params: &[],
+}];
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "layers_a",
+        default: 3,
+        min: 1,
+        description: "Number of directional keypads in part A",
+    },
+    Param {
+        name: "layers_b",
+        default: 26,
+        min: 1,
+        description: "Number of directional keypads in part B",
+    },
+];
+
+pub struct Day;
 
-    (
-        solve_ab(&codes, 3).to_string(),
-        solve_ab(&codes, 26).to_string(),
-    )
+impl Solver for Day {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Keypad Conundrum";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = Vec<&'input str>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let codes: Vec<&str> = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_num, line)| {
+                let code = line.trim();
+                match code.strip_suffix('A') {
+                    Some(num) if !num.is_empty() && num.chars().all(|ch| ch.is_ascii_digit()) => {
+                        Ok(code)
+                    }
+                    _ => Err(ParseError::at(
+                        line_num,
+                        line,
+                        code,
+                        "door code like \"029A\"",
+                    )),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(codes)
+    }
+
+    fn part_a(&self, codes: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_ab(codes, params.get("layers_a")).to_string()
+    }
+
+    fn part_b(&self, codes: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_ab(codes, params.get("layers_b")).to_string()
+    }
 }
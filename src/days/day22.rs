@@ -16,7 +16,12 @@
 
 use std::collections::HashMap;
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::ParseError,
+    input::Input,
+    solver::{Example, Param, Params, Solver},
+};
 
 fn next(secret: i64) -> i64 {
     let secret = prune(mix(secret, secret * 64));
@@ -32,26 +37,27 @@ fn prune(secret: i64) -> i64 {
     secret % 16777216
 }
 
-fn solve_a(inits: &[i64]) -> i64 {
+fn solve_a(inits: &[i64], iterations: usize) -> i64 {
     inits
         .iter()
         .copied()
         .map(|init| {
             std::iter::successors(Some(init), |secret| Some(next(*secret)))
-                .nth(2000)
+                .nth(iterations)
                 .unwrap()
         })
         .sum()
 }
 
-fn solve_b(inits: &[i64]) -> i64 {
+fn solve_b(inits: &[i64], iterations: usize) -> i64 {
     let mut solutions: HashMap<u32, i64> = HashMap::new();
     let mut best_profit = 0;
 
     for init in inits.iter().copied() {
+        cancel::check();
         let triggers: HashMap<u32, i64> =
             std::iter::successors(Some(init), |secret| Some(next(*secret)))
-                .take(2001)
+                .take(iterations + 1)
                 .scan((0, 0), |(trigger_key, p4), secret| {
                     let price = secret % 10;
                     *trigger_key = ((*trigger_key & 0x7fff) << 5) | ((price - *p4 + 10) as u32);
@@ -77,12 +83,56 @@ fn solve_b(inits: &[i64]) -> i64 {
     best_profit
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let inits: Vec<i64> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.trim().parse().unwrap())
-        .collect();
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "
+1
+10
+100
+2024
+",
+        part_a: Some("37327623"),
+        part_b: None,
+        params: &[],
+    },
+    Example {
+        input: "
+1
+2
+3
+2024
+",
+        part_a: None,
+        part_b: Some("23"),
+        params: &[],
+    },
+];
+
+const PARAMS: &[Param] = &[Param {
+    name: "iterations",
+    default: 2000,
+    min: 0,
+    description: "Number of new secret numbers each buyer generates",
+}];
+
+pub struct Day;
 
-    (solve_a(&inits).to_string(), solve_b(&inits).to_string())
+impl Solver for Day {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Market";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    const PARAMS: &'static [Param] = PARAMS;
+    type Parsed<'input> = Vec<i64>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        input.ints()
+    }
+
+    fn part_a(&self, inits: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_a(inits, params.get("iterations")).to_string()
+    }
+
+    fn part_b(&self, inits: &Self::Parsed<'_>, params: &Params) -> String {
+        solve_b(inits, params.get("iterations")).to_string()
+    }
 }
@@ -16,7 +16,12 @@
 
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-use crate::common::Solution;
+use crate::{
+    cancel,
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 fn solve_a(connections: &HashMap<&str, HashSet<&str>>) -> usize {
     let groups: BTreeSet<BTreeSet<&str>> = connections
@@ -40,6 +45,7 @@ fn solve_a(connections: &HashMap<&str, HashSet<&str>>) -> usize {
 fn solve_b(connections: &HashMap<&str, HashSet<&str>>) -> String {
     let groups: Vec<BTreeSet<&str>> =
         connections.keys().fold(Vec::new(), |mut groups, computer| {
+            cancel::check();
             let mut new_groups = Vec::new();
             for group in groups.iter_mut() {
                 if group.iter().all(|a| connections[a].contains(computer)) {
@@ -62,19 +68,81 @@ fn solve_b(connections: &HashMap<&str, HashSet<&str>>) -> String {
     names.join(",")
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let connections: HashMap<&str, HashSet<&str>> = lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .fold(HashMap::new(), |mut connections, line| {
-            let (a, b) = line.trim().split_once('-').unwrap();
-            connections.entry(a).or_default().insert(b);
-            connections.entry(b).or_default().insert(a);
-            connections
-        });
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+",
+    part_a: Some("7"),
+    part_b: Some("co,de,ka,ta"),
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "LAN Party";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = HashMap<&'input str, HashSet<&'input str>>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let connections: HashMap<&str, HashSet<&str>> = input
+            .numbered_lines()
+            .filter(|(_, line)| !line.is_empty())
+            .try_fold(
+                HashMap::new(),
+                |mut connections: HashMap<_, HashSet<_>>, (line_num, line)| {
+                    let (a, b) = line
+                        .trim()
+                        .split_once('-')
+                        .ok_or_else(|| ParseError::end_of_line(line_num, line, "'-'"))?;
+                    connections.entry(a).or_default().insert(b);
+                    connections.entry(b).or_default().insert(a);
+                    Ok::<_, ParseError>(connections)
+                },
+            )?;
+        if connections.is_empty() {
+            return Err(ParseError::end_of_input(input, "connection"));
+        }
+        Ok(connections)
+    }
+
+    fn part_a(&self, connections: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(connections).to_string()
+    }
 
-    (
-        solve_a(&connections).to_string(),
-        solve_b(&connections).to_string(),
-    )
+    fn part_b(&self, connections: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(connections)
+    }
 }
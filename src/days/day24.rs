@@ -16,10 +16,14 @@
 
 use std::collections::{BTreeSet, HashMap};
 
-use crate::common::Solution;
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
 
 #[derive(Debug, Eq, PartialEq)]
-struct Gate<'gate> {
+pub struct Gate<'gate> {
     a: &'gate str,
     b: &'gate str,
     op: Op,
@@ -146,47 +150,136 @@ fn solve_b<'gate>(init: HashMap<&'gate str, bool>, gates: &HashMap<&'gate str, G
     wrong.join(",")
 }
 
-pub fn solve(lines: &[String]) -> Solution {
-    let init: HashMap<&str, bool> = lines
-        .iter()
-        .skip_while(|line| line.is_empty())
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            let (name, value) = line.split_once(':').unwrap();
-            (name.trim(), value.trim() == "1")
-        })
-        .collect();
-    let gates: HashMap<&str, Gate> = lines
-        .iter()
-        .skip_while(|line| line.is_empty())
-        .skip_while(|line| !line.is_empty())
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let (lhs, rhs) = line.split_once("->").unwrap();
-            let (a, op_b) = lhs.split_once(' ').unwrap();
-            let (op, b) = op_b.split_once(' ').unwrap();
-            let (a, b) = (a.trim(), b.trim());
-            let (a, b) = (std::cmp::min(a, b), std::cmp::max(a, b));
-            (
-                rhs.trim(),
-                Gate {
-                    a,
-                    b,
-                    op: match op.trim() {
-                        "AND" => Op::And,
-                        "OR" => Op::Or,
-                        "XOR" => Op::Xor,
-                        _ => unreachable!(),
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj
+",
+    part_a: Some("2024"),
+    part_b: None,
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Crossed Wires";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (
+        HashMap<&'input str, bool>,
+        HashMap<&'input str, Gate<'input>>,
+    );
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let mut blocks = input.blocks();
+        let init: HashMap<&str, bool> = blocks
+            .next()
+            .into_iter()
+            .flat_map(|block| block.numbered_lines())
+            .map(|(line_num, line)| {
+                let (name, value) = line
+                    .split_once(':')
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "':'"))?;
+                match value.trim() {
+                    "0" => Ok((name.trim(), false)),
+                    "1" => Ok((name.trim(), true)),
+                    value => Err(ParseError::at(line_num, line, value, "'0' or '1'")),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        let gates: HashMap<&str, Gate> = blocks
+            .flat_map(|block| block.numbered_lines())
+            .map(|(line_num, line)| {
+                let (lhs, rhs) = line
+                    .split_once("->")
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "\"->\""))?;
+                let (a, op_b) = lhs
+                    .trim()
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::at(line_num, line, lhs, "\"<wire> <op> <wire>\""))?;
+                let (op, b) = op_b
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::at(line_num, line, lhs, "\"<wire> <op> <wire>\""))?;
+                let (a, b) = (a.trim(), b.trim());
+                let (a, b) = (std::cmp::min(a, b), std::cmp::max(a, b));
+                Ok((
+                    rhs.trim(),
+                    Gate {
+                        a,
+                        b,
+                        op: match op.trim() {
+                            "AND" => Op::And,
+                            "OR" => Op::Or,
+                            "XOR" => Op::Xor,
+                            op => {
+                                return Err(ParseError::at(
+                                    line_num,
+                                    line,
+                                    op,
+                                    "\"AND\", \"OR\" or \"XOR\"",
+                                ))
+                            }
+                        },
                     },
-                },
-            )
-        })
-        .collect();
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((init, gates))
+    }
 
-    (
-        solve_a(init.clone(), &gates, &HashMap::new())
+    fn part_a(&self, (init, gates): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(init.clone(), gates, &HashMap::new())
             .unwrap()
-            .to_string(),
-        solve_b(init, &gates).to_string(),
-    )
+            .to_string()
+    }
+
+    fn part_b(&self, (init, gates): &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(init.clone(), gates)
+    }
 }
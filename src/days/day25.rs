@@ -14,41 +14,114 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::common::Solution;
-
-pub fn solve(lines: &[String]) -> Solution {
-    let (locks, keys, _): (Vec<[u8; 5]>, Vec<[u8; 5]>, _) =
-        lines.iter().filter(|line| !line.is_empty()).fold(
-            (Vec::new(), Vec::new(), Vec::new()),
-            |(mut locks, mut keys, mut buf), line| {
-                buf.push(line);
-                if buf.len() == 7 {
-                    let cols: [u8; 5] = [0, 1, 2, 3, 4].map(|c| {
-                        buf.iter()
-                            .filter(|col| col.chars().nth(c).unwrap() == '#')
-                            .count() as u8
-                    });
-                    if buf[0] == "#####" {
-                        locks.push(cols);
-                    } else if buf.last().map(|s| s.as_str()) == Some("#####") {
-                        keys.push(cols);
-                    } else {
-                        unreachable!()
-                    }
-                    buf.clear();
+use crate::{
+    common::{check_block_shape, ParseError},
+    input::Input,
+    solver::{Example, Params, Solver},
+};
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+",
+    part_a: Some("3"),
+    part_b: None,
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Code Chronicle";
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = (Vec<[u8; 5]>, Vec<[u8; 5]>);
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        let mut locks = Vec::new();
+        let mut keys = Vec::new();
+        for block in input.blocks() {
+            check_block_shape(&block, 7, 5)?;
+            for (line_num, line) in block.numbered_lines() {
+                if let Some((c, ch)) = line.char_indices().find(|(_, ch)| !"#.".contains(*ch)) {
+                    return Err(ParseError::new(
+                        line_num,
+                        c + 1,
+                        "'#' or '.'",
+                        format!("{ch:?}"),
+                    ));
                 }
-                (locks, keys, buf)
-            },
-        );
-
-    let solution_a: usize = locks
-        .iter()
-        .map(|lock| {
-            keys.iter()
-                .filter(|key| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= 7))
-                .count()
-        })
-        .sum();
-
-    (solution_a.to_string(), "".to_string())
+            }
+            let rows: Vec<&str> = block.lines().collect();
+            let cols: [u8; 5] = [0, 1, 2, 3, 4]
+                .map(|c| rows.iter().filter(|row| row.as_bytes()[c] == b'#').count() as u8);
+            if rows[0] == "#####" {
+                locks.push(cols);
+            } else if rows[6] == "#####" {
+                keys.push(cols);
+            } else {
+                return Err(ParseError::at(
+                    block.first_line() + 6,
+                    rows[6],
+                    rows[6],
+                    "lock or key ending in \"#####\"",
+                ));
+            }
+        }
+        Ok((locks, keys))
+    }
+
+    fn part_a(&self, (locks, keys): &Self::Parsed<'_>, _params: &Params) -> String {
+        locks
+            .iter()
+            .map(|lock| {
+                keys.iter()
+                    .filter(|key| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= 7))
+                    .count()
+            })
+            .sum::<usize>()
+            .to_string()
+    }
+
+    fn part_b(&self, _: &Self::Parsed<'_>, _params: &Params) -> String {
+        String::new()
+    }
 }
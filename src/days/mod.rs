@@ -14,6 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::solver::DaySolver;
+#[cfg(test)]
+use crate::solver::Part;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -40,22 +44,92 @@ pub mod day23;
 pub mod day24;
 pub mod day25;
 
+/**
+ * Invoke the macro `$callback` with the module names of all days, in order.
+ * The solver registry, the benchmarks and the integration tests are all
+ * generated from this list, so they can't disagree on which days exist. The
+ * modules themselves are declared above rather than by the macro, since
+ * rustfmt does not format modules declared in macros.
+ */
+#[macro_export]
+macro_rules! with_days {
+    ($callback:ident) => {
+        $callback!(
+            day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+            day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24,
+            day25,
+        );
+    };
+}
+
 macro_rules! days {
-    ($($day_mod:ident),*) => {
-        pub fn get_solver(day: u8) -> Option<fn(&[String]) -> crate::common::Solution> {
+    ($($day_mod:ident),* $(,)?) => {
+        pub fn get_solver(day: u8) -> Option<&'static dyn DaySolver> {
             match format!("day{:02}", day).as_str() {
-                $(stringify!($day_mod) => Some($day_mod::solve),)*
+                $(stringify!($day_mod) => Some(&$day_mod::Day),)*
                     _ => None,
             }
         }
+
+        #[cfg(test)]
+        mod example_tests {
+            $(
+                #[test]
+                fn $day_mod() {
+                    super::check_examples(&super::$day_mod::Day);
+                }
+            )*
+        }
     };
 }
 
+/**
+ * Assert that every example of `solver` gives the expected answers.
+ */
+#[cfg(test)]
+fn check_examples(solver: &dyn DaySolver) {
+    let day = solver.day();
+    assert!(!solver.examples().is_empty(), "Day {day} has no examples");
+    for (i, example) in solver.examples().iter().enumerate() {
+        let input = example.input();
+        let params = solver.example_params(example);
+        let parsed = solver
+            .parse(&input)
+            .unwrap_or_else(|e| panic!("Example {}: {e}", i + 1));
+        for part in Part::ALL {
+            if let Some(expected) = example.answer(part) {
+                assert_eq!(
+                    parsed.part(part, &params),
+                    expected,
+                    "Incorrect solution for day {day}{part}, example {}",
+                    i + 1
+                );
+            }
+        }
+    }
+}
+
 pub fn all_numbers() -> Vec<u8> {
     (1..=25).filter(|&day| get_solver(day).is_some()).collect()
 }
 
-days!(
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
-);
+with_days!(days);
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn every_day_module_is_registered() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days");
+        let mut files: Vec<u8> = std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|entry| {
+                let name = entry.unwrap().file_name().into_string().ok()?;
+                name.strip_prefix("day")?.strip_suffix(".rs")?.parse().ok()
+            })
+            .collect();
+        files.sort();
+        assert_eq!(files, super::all_numbers());
+    }
+}
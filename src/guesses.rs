@@ -0,0 +1,264 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::client::Outcome;
+use adventofcode_2024::common::parse_token;
+use adventofcode_2024::common::ParseError;
+use adventofcode_2024::solver::Part;
+
+/**
+ * A submitted answer that the website evaluated.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Guess {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/**
+ * Why an answer should not be submitted.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    AlreadyGuessed { outcome: Outcome },
+    NotBelow { too_high: String },
+    NotAbove { too_low: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved { answer } => write!(f, "already solved with answer {answer}"),
+            Self::AlreadyGuessed { outcome } => {
+                write!(f, "this answer was already guessed and was {outcome}")
+            }
+            Self::NotBelow { too_high } => {
+                write!(f, "{too_high} was already too high")
+            }
+            Self::NotAbove { too_low } => write!(f, "{too_low} was already too low"),
+        }
+    }
+}
+
+/**
+ * The history of evaluated guesses, stored as tab-separated lines of day,
+ * part, outcome and answer, for example `1 A too-low 1234` with tabs between
+ * the fields.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GuessHistory {
+    guesses: Vec<Guess>,
+}
+
+impl GuessHistory {
+    /**
+     * Parse the text of a history file. The file is read as written, without
+     * [Input](adventofcode_2024::input::Input) normalization, since trailing spaces are
+     * part of the answer.
+     */
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let guesses = (1..)
+            .zip(source.lines())
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(line_num, line)| {
+                let mut fields = line.splitn(4, '\t');
+                let day = parse_token(line_num, line, fields.next(), "day number")?;
+                let part = parse_token(line_num, line, fields.next(), "'A' or 'B'")?;
+                let outcome = parse_token(
+                    line_num,
+                    line,
+                    fields.next(),
+                    "\"correct\", \"wrong\", \"too-high\" or \"too-low\"",
+                )?;
+                let answer = fields
+                    .next()
+                    .ok_or_else(|| ParseError::end_of_line(line_num, line, "answer"))?;
+                Ok(Guess {
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    outcome,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Self { guesses })
+    }
+
+    /**
+     * Load the history from `path`, which is empty if the file does not exist.
+     */
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        match std::fs::read_to_string(path) {
+            Ok(source) => Self::parse(&source)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+     * Check whether `answer` could be correct, given the earlier guesses.
+     * Answers are compared numerically with too-high and too-low guesses if
+     * both are integers.
+     */
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), Refusal> {
+        let guesses = self
+            .guesses
+            .iter()
+            .filter(|guess| guess.day == day && guess.part == part);
+        for guess in guesses {
+            let refusal = match (
+                &guess.outcome,
+                guess.answer.parse::<i128>(),
+                answer.parse::<i128>(),
+            ) {
+                (Outcome::Correct, _, _) => Some(Refusal::AlreadySolved {
+                    answer: guess.answer.clone(),
+                }),
+                (outcome, _, _) if guess.answer == answer => Some(Refusal::AlreadyGuessed {
+                    outcome: outcome.clone(),
+                }),
+                (Outcome::TooHigh, Ok(too_high), Ok(answer)) if answer >= too_high => {
+                    Some(Refusal::NotBelow {
+                        too_high: guess.answer.clone(),
+                    })
+                }
+                (Outcome::TooLow, Ok(too_low), Ok(answer)) if answer <= too_low => {
+                    Some(Refusal::NotAbove {
+                        too_low: guess.answer.clone(),
+                    })
+                }
+                _ => None,
+            };
+            if let Some(refusal) = refusal {
+                return Err(refusal);
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Add `guess` to the history and append it to the file at `path`.
+     */
+    pub fn record(&mut self, path: &Path, guess: Guess) -> Result<(), std::io::Error> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            guess.day, guess.part, guess.outcome, guess.answer
+        )?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Guess;
+    use super::GuessHistory;
+    use super::Refusal;
+    use crate::client::Outcome;
+    use adventofcode_2024::solver::Part;
+
+    fn history() -> GuessHistory {
+        GuessHistory::parse(
+            "# day\tpart\toutcome\tanswer\n\
+             3\tA\ttoo-low\t100\n\
+             3\tA\ttoo-high\t200\n\
+             3\tA\twrong\t150\n\
+             3\tB\tcorrect\t42\n\
+             23\tA\twrong\tab,cd\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn allows_answers_between_bounds() {
+        assert_eq!(history().check(3, Part::A, "101"), Ok(()));
+        assert_eq!(history().check(3, Part::A, "199"), Ok(()));
+        assert_eq!(history().check(4, Part::A, "150"), Ok(()));
+        assert_eq!(history().check(23, Part::A, "ab,ce"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_bad_answers() {
+        assert_eq!(
+            history().check(3, Part::A, "150"),
+            Err(Refusal::AlreadyGuessed {
+                outcome: Outcome::Wrong
+            })
+        );
+        assert_eq!(
+            history().check(3, Part::A, "100"),
+            Err(Refusal::AlreadyGuessed {
+                outcome: Outcome::TooLow
+            })
+        );
+        assert_eq!(
+            history().check(3, Part::A, "250"),
+            Err(Refusal::NotBelow {
+                too_high: "200".to_string()
+            })
+        );
+        assert_eq!(
+            history().check(3, Part::A, "-5"),
+            Err(Refusal::NotAbove {
+                too_low: "100".to_string()
+            })
+        );
+        assert_eq!(
+            history().check(3, Part::B, "43"),
+            Err(Refusal::AlreadySolved {
+                answer: "42".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn record_appends_to_file() {
+        let dir = std::env::temp_dir().join(format!("aoc-guesses-test-{}", std::process::id()));
+        let path = dir.join("guesses.tsv");
+        let mut history = GuessHistory::load(&path).unwrap();
+        let guess = Guess {
+            day: 23,
+            part: Part::B,
+            answer: "co,de,ka,ta".to_string(),
+            outcome: Outcome::Wrong,
+        };
+        let padded = Guess {
+            day: 1,
+            part: Part::A,
+            answer: "12 ".to_string(),
+            outcome: Outcome::TooLow,
+        };
+        history.record(&path, guess.clone()).unwrap();
+        history.record(&path, padded.clone()).unwrap();
+        assert_eq!(GuessHistory::load(&path).unwrap(), history);
+        assert_eq!(history.guesses, vec![guess, padded]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,327 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs::File;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::common::check_rectangular;
+use crate::common::normalize;
+use crate::common::parse_token;
+use crate::common::Normalization;
+use crate::common::ParseError;
+use crate::util::grid::Grid;
+
+/**
+ * A puzzle input, held in one buffer. Its lines and blocks are views borrowed
+ * from the buffer.
+ *
+ * The text is [normalize]d when the input is created, so solvers never see a
+ * byte order mark, CR characters or trailing whitespace.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Input {
+    text: String,
+    normalization: Normalization,
+}
+
+impl Input {
+    pub fn new<S>(text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let (text, normalization) = normalize(text.into());
+        Self {
+            text,
+            normalization,
+        }
+    }
+
+    /**
+     * Read the input from the file at `path`, or from standard input if `path`
+     * is `-`.
+     */
+    pub fn read(path: &Path) -> Result<Self, std::io::Error> {
+        let mut text = String::new();
+        if path == Path::new("-") {
+            std::io::stdin().read_to_string(&mut text)?;
+        } else {
+            File::open(path)
+                .and_then(|mut file| file.read_to_string(&mut text))
+                .map_err(|e| {
+                    let message = match e.kind() {
+                        ErrorKind::NotFound => format!("Input file not found: {path:?}"),
+                        _ => format!("Failed to read input file {path:?}: {e}"),
+                    };
+                    std::io::Error::new(e.kind(), message)
+                })?;
+        }
+        Ok(Self::new(text))
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /**
+     * What was changed in the text to normalize it.
+     */
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /**
+     * The lines of the input, without line terminators.
+     */
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /**
+     * The lines of the input paired with their 1-based line numbers.
+     */
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines().enumerate().map(|(i, line)| (i + 1, line))
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    /**
+     * The sections of consecutive non-empty lines, separated by empty lines.
+     */
+    pub fn blocks(&self) -> impl Iterator<Item = Block<'_>> {
+        let mut lines = self.numbered_lines().peekable();
+        std::iter::from_fn(move || {
+            let (first_line, first) = lines.find(|(_, line)| !line.is_empty())?;
+            let mut last = first;
+            while let Some((_, line)) = lines.next_if(|(_, line)| !line.is_empty()) {
+                last = line;
+            }
+            let start = self.offset_of(first);
+            let end = self.offset_of(last) + last.len();
+            Some(Block {
+                first_line,
+                text: &self.text[start..end],
+            })
+        })
+    }
+
+    /**
+     * The non-empty lines of the input as a [Grid], with each character mapped
+     * to a cell by `f`. See [Grid::parse].
+     */
+    pub fn grid<T, F>(&self, expected: &str, f: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Grid::parse(self.numbered_lines(), expected, f)
+    }
+
+    /**
+     * The length of the non-empty lines of the input, or an error if they
+     * differ.
+     */
+    pub fn grid_width(&self) -> Result<usize, ParseError> {
+        check_rectangular(self.numbered_lines().filter(|(_, line)| !line.is_empty()))
+    }
+
+    /**
+     * Every integer in the input, in order. A `-` directly before an integer
+     * is its sign, unless the `-` follows a digit.
+     */
+    pub fn ints<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+    {
+        self.numbered_lines()
+            .flat_map(|(line_num, line)| {
+                int_tokens(line)
+                    .map(move |token| parse_token(line_num, line, Some(token), "integer"))
+            })
+            .collect()
+    }
+
+    fn offset_of(&self, line: &str) -> usize {
+        line.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+}
+
+/**
+ * A section of consecutive non-empty lines of an [Input].
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block<'input> {
+    first_line: usize,
+    text: &'input str,
+}
+
+impl<'input> Block<'input> {
+    /**
+     * The 1-based line number of the first line of the block in the input.
+     */
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    pub fn text(&self) -> &'input str {
+        self.text
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'input> {
+        self.text.lines()
+    }
+
+    /**
+     * The lines of the block paired with their 1-based line numbers in the
+     * input.
+     */
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'input str)> {
+        let first_line = self.first_line;
+        self.lines()
+            .enumerate()
+            .map(move |(i, line)| (first_line + i, line))
+    }
+}
+
+fn int_tokens(line: &str) -> impl Iterator<Item = &str> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let mut start = i;
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(&line[start..i])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::path::Path;
+
+    use super::Input;
+    use crate::common::ParseError;
+
+    #[test]
+    fn read_reports_missing_and_unreadable_files() {
+        let missing = Input::read(Path::new("no/such/input.in")).unwrap_err();
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+        assert_eq!(
+            missing.to_string(),
+            "Input file not found: \"no/such/input.in\""
+        );
+
+        let directory = Input::read(Path::new("src")).unwrap_err();
+        assert_ne!(directory.kind(), ErrorKind::NotFound);
+        assert!(
+            directory
+                .to_string()
+                .starts_with("Failed to read input file \"src\": "),
+            "{directory}"
+        );
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let input = Input::new("a\r\n\nb\n");
+        assert_eq!(
+            input.numbered_lines().collect::<Vec<_>>(),
+            vec![(1, "a"), (2, ""), (3, "b")]
+        );
+        assert_eq!(input.line_count(), 3);
+    }
+
+    #[test]
+    fn blocks_are_separated_by_empty_lines() {
+        let input = Input::new("\n47|53\n97|13\n\n\n75,47\n61\n");
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text(), "47|53\n97|13");
+        assert_eq!(blocks[0].first_line(), 2);
+        assert_eq!(
+            blocks[1].numbered_lines().collect::<Vec<_>>(),
+            vec![(6, "75,47"), (7, "61")]
+        );
+    }
+
+    #[test]
+    fn blocks_of_empty_input() {
+        assert_eq!(Input::new("").blocks().count(), 0);
+        assert_eq!(Input::new("\n\n").blocks().count(), 0);
+    }
+
+    #[test]
+    fn grid_skips_empty_lines() {
+        let input = Input::new("#.#\n.^.\n\n");
+        let grid = input.grid("any character", Some).unwrap();
+        assert_eq!(grid.to_string(), "#.#\n.^.\n");
+    }
+
+    #[test]
+    fn grid_must_be_rectangular() {
+        let input = Input::new("#.#\n.^\n");
+        assert_eq!(
+            input.grid("any character", Some),
+            Err(ParseError::new(2, 3, "3 columns", "end of line"))
+        );
+    }
+
+    #[test]
+    fn text_is_normalized() {
+        let input = Input::new("\u{feff}1 2\r\n\r\n3 \r\n");
+        assert_eq!(input.text(), "1 2\n\n3\n");
+        assert_eq!(input.blocks().count(), 2);
+        assert_eq!(input.normalization().crlf, 3);
+    }
+
+    #[test]
+    fn ints_with_signs() {
+        let input = Input::new("p=0,4 v=3,-3\nButton A: X+94, Y+34\n1-3 x: -12");
+        assert_eq!(
+            input.ints::<i64>(),
+            Ok(vec![0, 4, 3, -3, 94, 34, 1, 3, -12])
+        );
+    }
+
+    #[test]
+    fn ints_out_of_range() {
+        assert_eq!(
+            Input::new("1\n2 300").ints::<u8>(),
+            Err(ParseError::new(2, 3, "integer", "\"300\""))
+        );
+    }
+}
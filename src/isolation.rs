@@ -0,0 +1,107 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::panic::AssertUnwindSafe;
+use std::sync::Once;
+
+use adventofcode_2024::cancel::Cancelled;
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/**
+ * Run `f`, turning a panic into an error with the panic message and location.
+ *
+ * Panics caught this way are not printed by the panic hook; panics elsewhere,
+ * and on threads that are not inside `catch_panic`, are reported as usual.
+ */
+pub fn catch_panic<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> T,
+{
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                let message = payload_message(info.payload());
+                LAST_PANIC.set(Some(match info.location() {
+                    Some(location) => format!("{message} (at {location})"),
+                    None => message,
+                }));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+    result.map_err(|payload| {
+        LAST_PANIC
+            .take()
+            .unwrap_or_else(|| payload_message(&*payload))
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if payload.is::<Cancelled>() {
+        "cancelled".to_string()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn returns_result_if_no_panic() {
+        assert_eq!(catch_panic(|| 1 + 2), Ok(3));
+    }
+
+    #[test]
+    fn reports_panic_message_and_location() {
+        let line = line!() + 1;
+        let result: Result<(), String> = catch_panic(|| panic!("Bad input: {}", 42));
+        assert_eq!(
+            result,
+            Err(format!("Bad input: 42 (at {}:{}:57)", file!(), line))
+        );
+    }
+
+    #[test]
+    fn nested_catch_keeps_catching() {
+        let result = catch_panic(|| {
+            let inner: Result<(), String> = catch_panic(|| panic!("inner"));
+            assert!(inner.is_err());
+            panic!("outer")
+        });
+        assert!(result.unwrap_err().starts_with("outer"));
+    }
+}
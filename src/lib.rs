@@ -14,9 +14,28 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+/*!
+ * Solutions to Advent of Code 2024.
+ *
+ * [solve] solves a day's puzzle for an input, and [all_days] lists the days
+ * that have solvers along with their titles, parameters and examples. The
+ * [search] and [util] modules hold the general-purpose algorithms and helpers
+ * the solvers are built from.
+ */
+
+mod api;
+
+pub mod answers;
+pub mod cancel;
 pub mod common;
 pub mod days;
+pub mod input;
+pub mod search;
+pub mod solver;
+pub mod util;
 
-#[allow(unused)]
-mod search;
-mod util;
+pub use api::all_days;
+pub use api::solve;
+pub use api::Answers;
+pub use api::DayInfo;
+pub use api::Error;
@@ -14,67 +14,1125 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod cache;
+mod client;
 mod crate_info;
+mod guesses;
+#[cfg(test)]
+mod http_mock;
+mod isolation;
+mod memory;
+mod pool;
+mod report;
+mod scaffold;
+mod selection;
+mod watch;
 
 use clap::App;
 use clap::Arg;
+use clap::ArgMatches;
+use clap::SubCommand;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::io::Write;
 use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
+use crate::cache::build_id;
+use crate::cache::input_hash;
+use crate::cache::Cache;
+use crate::cache::Entry;
+use crate::cache::Key;
+use crate::client::read_session;
+use crate::client::wait_for_unlock;
+use crate::client::write_atomically;
+use crate::client::Client;
+use crate::client::Outcome;
+use crate::client::DEFAULT_BASE_URL;
 use crate::crate_info::crate_author;
 use crate::crate_info::crate_description;
 use crate::crate_info::crate_name;
 use crate::crate_info::crate_version;
+use crate::guesses::Guess;
+use crate::guesses::GuessHistory;
+use crate::isolation::catch_panic;
+use crate::memory::AllocStats;
+use crate::pool::map_ordered;
+use crate::pool::worker_count;
+use crate::report::DayReport;
+use crate::report::Format;
+use crate::report::Reporter;
+use crate::report::Timing;
+use crate::scaffold::add_day;
+use crate::selection::parse_days;
+use crate::selection::parse_input_override;
+use crate::watch::answer_changes;
+use crate::watch::InputWatcher;
+use adventofcode_2024::answers::Answers;
+use adventofcode_2024::answers::Verdict;
+use adventofcode_2024::cancel::is_cancelled;
+use adventofcode_2024::cancel::with_timeout;
 use adventofcode_2024::common::day_input_filename;
-use adventofcode_2024::common::get_file_lines;
 use adventofcode_2024::days;
+use adventofcode_2024::input::Input;
+use adventofcode_2024::solver::Param;
+use adventofcode_2024::solver::Params;
+use adventofcode_2024::solver::Part;
 
 fn main() -> Result<(), std::io::Error> {
     let cli = App::new(crate_name())
         .version(crate_version())
         .about(crate_description())
         .author(crate_author())
+        .arg(day_arg().help(r#"Days to run: a day number (1 - 25), a range like "1-10", a list like "3,7,12", "all" or "latest". Prefix a term with "!" to exclude those days. If omitted, all days are run."#))
+        .arg(input_file_arg())
+        .arg(input_arg())
         .arg(
-            Arg::with_name("day")
+            Arg::with_name("part")
+                .long("part")
                 .takes_value(true)
-                .help(r#"Day number (1 - 25) to run. If omitted, all days are run."#)
+                .possible_values(&["a", "b"])
+                .help(r#"Part of the puzzle to solve. If omitted, both parts are solved."#)
         )
         .arg(
-            Arg::with_name("input-file")
+            Arg::with_name("format")
+                .long("format")
                 .takes_value(true)
-                .help(r#"Path to a file containing input for the chosen day. Use "-" for standard input; omit to use "./inputs/day<day>.in"."#)
+                .possible_values(&["text", "json", "csv"])
+                .default_value("text")
+                .help(r#"Output format. "json" emits an array with one object per day, "csv" one row per day."#)
+        )
+        .arg(
+            Arg::with_name("time")
+                .long("time")
+                .help(r#"Report the time spent parsing and solving each part, and the total for all days."#)
+        )
+        .arg(
+            Arg::with_name("repeat")
+                .long("repeat")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("1")
+                .help(r#"Run each step N times and report the min, median and max time."#)
+        )
+        .arg(jobs_arg())
+        .arg(timeout_arg())
+        .arg(cache_arg())
+        .arg(no_cache_arg())
+        .arg(cache_file_arg())
+        .arg(
+            Arg::with_name("example")
+                .long("example")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1)
+                .value_name("N")
+                .conflicts_with_all(&["input-file", "input"])
+                .help(r#"Run the published examples instead of the inputs, and check their answers. If N is given, only the Nth example of each day is run."#)
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .conflicts_with("example")
+                .help(r#"Keep running, and run a day again whenever the content of its input file changes, showing how the answers changed."#)
+        )
+        .arg(
+            Arg::with_name("param")
+                .long("param")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("KEY=VALUE")
+                .help(r#"Override a puzzle parameter, such as a grid size, for each selected day that declares it. May be given more than once. Examples use their own parameters unless overridden."#)
+        )
+        .subcommand(
+            SubCommand::with_name("check")
+                .about("Compare answers with the known answers registry")
+                .arg(day_arg().help(r#"Days to check, in the same form as for running them. If omitted, all days are checked."#))
+                .arg(input_file_arg())
+                .arg(input_arg())
+                .arg(jobs_arg())
+                .arg(timeout_arg())
+                .arg(cache_arg())
+                .arg(no_cache_arg())
+                .arg(cache_file_arg())
+                .arg(
+                    Arg::with_name("answers")
+                        .long("answers")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .default_value("answers.toml")
+                        .help(r#"Path to the known answers registry."#)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("fetch")
+                .about("Download puzzle inputs to ./inputs/day<day>.in, waiting for each puzzle to unlock")
+                .arg(day_arg().help(r#"Days to download, in the same form as for running them. If omitted, all days are downloaded."#))
+                .arg(session_file_arg())
+                .arg(base_url_arg())
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .help(r#"Overwrite input files that already exist."#)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("submit")
+                .about("Submit an answer, unless earlier guesses show that it is wrong")
+                .arg(
+                    Arg::with_name("day")
+                        .required(true)
+                        .help(r#"Day number (1 - 25) to submit an answer for."#)
+                )
+                .arg(
+                    Arg::with_name("part")
+                        .required(true)
+                        .possible_values(&["a", "b"])
+                        .help(r#"Part of the puzzle to submit an answer for."#)
+                )
+                .arg(
+                    Arg::with_name("answer")
+                        .help(r#"The answer to submit. If omitted, the answer is computed from the input."#)
+                )
+                .arg(
+                    Arg::with_name("input-file")
+                        .long("input-file")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help(r#"Path to a file containing input to compute the answer from. Use "-" for standard input; omit to use "./inputs/day<day>.in"."#)
+                )
+                .arg(session_file_arg())
+                .arg(base_url_arg())
+                .arg(
+                    Arg::with_name("history")
+                        .long("history")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .default_value("inputs/guesses.tsv")
+                        .help(r#"File to record submitted answers and their outcomes in."#)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Create ./src/days/day<day>.rs from a template and register the new day")
+                .arg(
+                    Arg::with_name("day")
+                        .required(true)
+                        .help(r#"Day number (1 - 25) to create."#)
+                )
+                .arg(
+                    Arg::with_name("title")
+                        .help(r#"Title of the puzzle, without the "Day N:" prefix."#)
+                )
         );
 
     let matches = cli.get_matches();
+    if let Some(check_matches) = matches.subcommand_matches("check") {
+        let failed = check(check_matches)?;
+        std::process::exit(failed.min(255) as i32);
+    }
+    if let Some(fetch_matches) = matches.subcommand_matches("fetch") {
+        let failed = fetch(fetch_matches);
+        std::process::exit(failed.min(255) as i32);
+    }
+    if let Some(new_matches) = matches.subcommand_matches("new") {
+        new_day(new_matches)?;
+        return Ok(());
+    }
+    if let Some(submit_matches) = matches.subcommand_matches("submit") {
+        let correct = submit(submit_matches)?;
+        std::process::exit(if correct { 0 } else { 1 });
+    }
 
-    if let Some(day) = matches.value_of("day") {
-        run_day(
-            day.parse::<u8>().expect("Invalid day number: {day}"),
-            matches.value_of("input-file").map(Path::new),
-        )
+    let part: Option<Part> = matches.value_of("part").map(|p| p.parse().unwrap());
+    let format: Format = matches.value_of("format").unwrap().parse().unwrap();
+    let repeat: usize = matches
+        .value_of("repeat")
+        .unwrap()
+        .parse()
+        .expect("Invalid repeat count");
+    let time = matches.is_present("time") || repeat > 1;
+    let options = RunOptions {
+        part,
+        repeat,
+        timeout: parse_timeout(&matches),
+    };
+
+    let jobs = parse_jobs(&matches);
+
+    let selected = select_days(&matches, &days::all_numbers());
+    let overrides = param_overrides(
+        &matches,
+        &selected.iter().map(|(day, _)| *day).collect::<Vec<_>>(),
+    );
+
+    let days: Vec<(u8, Source, Params)> = if matches.is_present("example") {
+        let number: Option<usize> = matches.value_of("example").map(|n| {
+            n.parse()
+                .unwrap_or_else(|_| usage_error(format!("Invalid example number: {n}")))
+        });
+        selected
+            .into_iter()
+            .flat_map(|(day, _)| {
+                let solver = days::get_solver(day).unwrap();
+                let overrides = &overrides;
+                number
+                    .map(|n| n..=n)
+                    .unwrap_or(1..=solver.examples().len())
+                    .map(move |n| {
+                        let params = match n.checked_sub(1).and_then(|i| solver.examples().get(i)) {
+                            Some(example) => solver.example_params(example),
+                            None => Params::defaults(solver.params()),
+                        };
+                        (
+                            day,
+                            Source::Example(n),
+                            with_overrides(day, params, overrides),
+                        )
+                    })
+            })
+            .collect()
     } else {
-        run_all_days()
+        selected
+            .into_iter()
+            .map(|(day, input_path)| {
+                let params = with_overrides(day, default_params(day), &overrides);
+                (day, Source::File(input_path), params)
+            })
+            .collect()
+    };
+
+    let cache = open_cache(&matches)?;
+    if matches.is_present("watch") {
+        return watch_days(format, time, &days, options, jobs, cache.as_ref());
     }
+
+    let mut reporter = Reporter::new(format, std::io::stdout().lock()).with_time(time);
+    reporter.begin()?;
+    let start = Instant::now();
+    run_days(&mut reporter, &days, options, jobs, cache.as_ref())?;
+    if matches.is_present("jobs") {
+        reporter.set_wall_time(start.elapsed());
+    }
+    reporter.finish()?;
+    if let Some(cache) = cache {
+        cache.save()?;
+    }
+
+    if reporter.failed() > 0 {
+        std::process::exit(reporter.failed().min(255) as i32);
+    }
+    Ok(())
+}
+
+fn day_arg() -> Arg<'static, 'static> {
+    Arg::with_name("day").takes_value(true).value_name("DAYS")
 }
 
-fn run_day(day: u8, input_path: Option<&Path>) -> Result<(), std::io::Error> {
+fn input_file_arg() -> Arg<'static, 'static> {
+    Arg::with_name("input-file")
+        .takes_value(true)
+        .help(r#"Path to a file containing input for the chosen day, if only one day is chosen. Use "-" for standard input; omit to use "./inputs/day<day>.in"."#)
+}
+
+fn input_arg() -> Arg<'static, 'static> {
+    Arg::with_name("input")
+        .long("input")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("DAY=PATH")
+        .help(r#"Path to a file containing input for day DAY. May be given once per day."#)
+}
+
+fn jobs_arg() -> Arg<'static, 'static> {
+    Arg::with_name("jobs")
+        .long("jobs")
+        .short("j")
+        .takes_value(true)
+        .value_name("N")
+        .help(r#"Run up to N days in parallel; 0 means one per CPU. Results are still reported in day order."#)
+}
+
+fn timeout_arg() -> Arg<'static, 'static> {
+    Arg::with_name("timeout")
+        .long("timeout")
+        .takes_value(true)
+        .value_name("SECS")
+        .help(r#"Give up on a day that takes longer than SECS seconds, report it as a TIMEOUT and move on to the next day."#)
+}
+
+fn cache_arg() -> Arg<'static, 'static> {
+    Arg::with_name("cache")
+        .long("cache")
+        .help(r#"Serve answers from the result cache when the input, parameters and build are unchanged, and store new answers in it."#)
+}
+
+fn no_cache_arg() -> Arg<'static, 'static> {
+    Arg::with_name("no-cache")
+        .long("no-cache")
+        .help(r#"Recompute every answer, and replace the cached answers with the new ones."#)
+}
+
+fn cache_file_arg() -> Arg<'static, 'static> {
+    Arg::with_name("cache-file")
+        .long("cache-file")
+        .takes_value(true)
+        .value_name("FILE")
+        .default_value("inputs/cache.tsv")
+        .help(r#"File to store the result cache in."#)
+}
+
+fn session_file_arg() -> Arg<'static, 'static> {
+    Arg::with_name("session-file")
+        .long("session-file")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(r#"File containing the session cookie. If omitted, the cookie is read from the SESSION_COOKIE environment variable."#)
+}
+
+fn base_url_arg() -> Arg<'static, 'static> {
+    Arg::with_name("base-url")
+        .long("base-url")
+        .takes_value(true)
+        .value_name("URL")
+        .default_value(DEFAULT_BASE_URL)
+        .help(r#"Base URL of the Advent of Code website."#)
+}
+
+fn parse_timeout(matches: &ArgMatches) -> Option<Duration> {
+    matches.value_of("timeout").map(|secs| {
+        secs.parse()
+            .ok()
+            .filter(|secs: &f64| secs.is_finite() && *secs > 0.0)
+            .map(Duration::from_secs_f64)
+            .unwrap_or_else(|| usage_error(format!("Invalid timeout: {secs}")))
+    })
+}
+
+fn parse_jobs(matches: &ArgMatches) -> usize {
+    worker_count(
+        matches
+            .value_of("jobs")
+            .map(|jobs| {
+                jobs.parse()
+                    .unwrap_or_else(|_| usage_error(format!("Invalid job count: {jobs}")))
+            })
+            .unwrap_or(1),
+    )
+}
+
+/**
+ * The selected days in ascending order, each with its input file if one was
+ * given. Every day must be one of the `available` days. Exits with a usage
+ * error if the selection is invalid.
+ */
+fn select_days(matches: &ArgMatches, available: &[u8]) -> Vec<(u8, Option<PathBuf>)> {
+    let days = parse_days(matches.value_of("day").unwrap_or("all"), available)
+        .unwrap_or_else(|e| usage_error(e));
+
+    let mut inputs: HashMap<u8, PathBuf> = matches
+        .values_of("input")
+        .into_iter()
+        .flatten()
+        .map(|s| parse_input_override(s, available).unwrap_or_else(|e| usage_error(e)))
+        .collect();
+    if let Some(path) = matches.value_of("input-file") {
+        if days.len() != 1 {
+            usage_error("An input file can only be given for a single day; use --input DAY=PATH");
+        }
+        inputs.insert(days[0], PathBuf::from(path));
+    }
+    if let Some(day) = inputs.keys().find(|day| !days.contains(day)) {
+        usage_error(format!(
+            "Input file given for day {day}, which is not selected"
+        ));
+    }
+
+    days.into_iter()
+        .map(|day| (day, inputs.remove(&day)))
+        .collect()
+}
+
+/**
+ * The `--param` overrides as name-value pairs. Exits with a usage error if one
+ * is malformed or not declared by any of `days`.
+ */
+fn param_overrides<'a>(matches: &'a ArgMatches, days: &[u8]) -> Vec<(&'a str, &'a str)> {
+    matches
+        .values_of("param")
+        .into_iter()
+        .flatten()
+        .map(|s| {
+            let (name, value) = s.split_once('=').unwrap_or_else(|| {
+                usage_error(format!("Invalid parameter: {s}; expected KEY=VALUE"))
+            });
+            if !days
+                .iter()
+                .any(|day| declared_params(*day).iter().any(|spec| spec.name == name))
+            {
+                usage_error(format!(
+                    "Parameter {name} is not declared by any selected day"
+                ));
+            }
+            (name, value)
+        })
+        .collect()
+}
+
+/**
+ * `params` with the overrides that `day` declares applied. Exits with a usage
+ * error if a value is invalid.
+ */
+fn with_overrides(day: u8, params: Params, overrides: &[(&str, &str)]) -> Params {
+    let specs = declared_params(day);
+    overrides
+        .iter()
+        .filter(|(name, _)| specs.iter().any(|spec| spec.name == *name))
+        .try_fold(params, |params, (name, value)| {
+            params.with(specs, name, value)
+        })
+        .unwrap_or_else(|e| usage_error(format!("Day {day}: {e}")))
+}
+
+fn declared_params(day: u8) -> &'static [Param] {
+    days::get_solver(day)
+        .unwrap_or_else(|| panic!("Unknown day: {day}"))
+        .params()
+}
+
+fn default_params(day: u8) -> Params {
+    Params::defaults(declared_params(day))
+}
+
+fn usage_error<M: Display>(message: M) -> ! {
+    clap::Error::with_description(&message.to_string(), clap::ErrorKind::InvalidValue).exit()
+}
+
+/**
+ * The result cache of a run, shared by the days running in parallel. Cached
+ * answers are served unless `refresh` is set, and new answers are stored.
+ */
+struct ResultCache {
+    cache: Mutex<Cache>,
+    path: PathBuf,
+    refresh: bool,
+}
+
+impl ResultCache {
+    fn keys(day: u8, input: &Input, params: &Params) -> [Key; 2] {
+        let input_hash = input_hash(input);
+        Part::ALL.map(|part| Key {
+            day,
+            part,
+            input_hash,
+            params: params.to_string(),
+        })
+    }
+
+    /**
+     * Fill in the cached answers and timings of the parts selected by
+     * `run_part`.
+     */
+    fn serve<F>(&self, report: &mut DayReport, keys: &[Key; 2], run_part: F)
+    where
+        F: Fn(Part) -> bool,
+    {
+        if self.refresh {
+            return;
+        }
+        let cache = self.cache.lock().unwrap();
+        for key in keys.iter().filter(|key| run_part(key.part)) {
+            if let Some(entry) = cache.get(key) {
+                let answer = (
+                    Some(entry.answer.clone()),
+                    Some(Timing::new(vec![entry.time])),
+                );
+                match key.part {
+                    Part::A => (report.solution_a, report.time_a) = answer,
+                    Part::B => (report.solution_b, report.time_b) = answer,
+                }
+                report
+                    .time_parse
+                    .get_or_insert_with(|| Timing::new(vec![entry.time_parse]));
+                report.cached.push(key.part);
+            }
+        }
+    }
+
+    /**
+     * Store the answers that were computed for `report`.
+     */
+    fn store(&self, report: &DayReport, keys: &[Key; 2]) {
+        let Some(time_parse) = &report.time_parse else {
+            return;
+        };
+        let mut cache = self.cache.lock().unwrap();
+        for key in keys.iter().filter(|key| !report.cached.contains(&key.part)) {
+            let (solution, time) = match key.part {
+                Part::A => (&report.solution_a, &report.time_a),
+                Part::B => (&report.solution_b, &report.time_b),
+            };
+            if let (Some(solution), Some(time)) = (solution, time) {
+                cache.insert(
+                    key.clone(),
+                    Entry {
+                        answer: solution.clone(),
+                        time_parse: time_parse.median(),
+                        time: time.median(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), std::io::Error> {
+        self.cache.lock().unwrap().save(&self.path)
+    }
+}
+
+/**
+ * The result cache, if enabled with `--cache` or `--no-cache`.
+ */
+fn open_cache(matches: &ArgMatches) -> Result<Option<ResultCache>, std::io::Error> {
+    if !matches.is_present("cache") && !matches.is_present("no-cache") {
+        return Ok(None);
+    }
+    let path = PathBuf::from(matches.value_of("cache-file").unwrap());
+    let cache = Cache::load(&path, &build_id()?)?;
+    Ok(Some(ResultCache {
+        cache: Mutex::new(cache),
+        path,
+        refresh: matches.is_present("no-cache"),
+    }))
+}
+
+/**
+ * Where to get the input for a day.
+ */
+enum Source {
+    File(Option<PathBuf>),
+    Example(usize),
+}
+
+/**
+ * How to run each day: which parts to solve, how many times to run each step,
+ * and how long to wait for the day to finish.
+ */
+#[derive(Clone, Copy, Debug)]
+struct RunOptions {
+    part: Option<Part>,
+    repeat: usize,
+    timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    fn runs_part(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+fn solve_day(
+    day: u8,
+    input_path: Option<&Path>,
+    params: &Params,
+    options: RunOptions,
+    cache: Option<&ResultCache>,
+) -> DayReport {
+    let input_path = input_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| day_input_filename(day));
+    let mut report = DayReport {
+        day,
+        input_path,
+        ..Default::default()
+    };
+
+    match Input::read(&report.input_path) {
+        Ok(input) => {
+            report.normalization = input.normalization();
+            let run_part = |p| options.runs_part(p);
+            let keys = cache.map(|_| ResultCache::keys(day, &input, params));
+            if let (Some(cache), Some(keys)) = (cache, &keys) {
+                cache.serve(&mut report, keys, run_part);
+            }
+            let cached = report.cached.clone();
+            let run_part = |p| run_part(p) && !cached.contains(&p);
+            if Part::ALL.into_iter().any(run_part) {
+                solve_input(&mut report, input, run_part, params, options);
+                if let (Some(cache), Some(keys)) = (cache, &keys) {
+                    cache.store(&report, keys);
+                }
+            }
+        }
+        Err(e) => report.error = Some(e.to_string()),
+    }
+    report
+}
+
+/**
+ * Run the 1-based `number`th example of `day`, and fail if an answer differs
+ * from the expected one.
+ */
+fn solve_example(day: u8, number: usize, params: &Params, options: RunOptions) -> DayReport {
+    let mut report = DayReport {
+        day,
+        input_path: PathBuf::from(format!("<example {number}>")),
+        example: Some(number),
+        ..Default::default()
+    };
+    let examples = days::get_solver(day)
+        .unwrap_or_else(|| panic!("Unknown day: {day}"))
+        .examples();
+    let Some(example) = number.checked_sub(1).and_then(|i| examples.get(i)) else {
+        report.error = Some(format!("Day {day} has no example {number}"));
+        return report;
+    };
+
+    solve_input(
+        &mut report,
+        example.input(),
+        |p| options.runs_part(p) && example.answer(p).is_some(),
+        params,
+        options,
+    );
+    if report.error.is_none() {
+        let mismatches: Vec<String> =
+            [(Part::A, &report.solution_a), (Part::B, &report.solution_b)]
+                .into_iter()
+                .filter_map(|(p, solution)| match (solution, example.answer(p)) {
+                    (Some(solution), Some(expected)) if solution != expected => {
+                        Some(format!("Part {p}: expected {expected}, found {solution}"))
+                    }
+                    _ => None,
+                })
+                .collect();
+        if !mismatches.is_empty() {
+            report.error = Some(mismatches.join("; "));
+        }
+    }
+    report
+}
+
+/**
+ * Parse `input` and solve the parts selected by `run_part` with `params`,
+ * recording answers, timings and errors in `report`. With a timeout, this runs
+ * on a thread of its own, and only a timeout is recorded if it takes too long.
+ */
+fn solve_input<F>(
+    report: &mut DayReport,
+    input: Input,
+    run_part: F,
+    params: &Params,
+    options: RunOptions,
+) where
+    F: Fn(Part) -> bool,
+{
+    let parts: Vec<Part> = Part::ALL.into_iter().filter(|p| run_part(*p)).collect();
+    let mut solving = report.clone();
+    let params = params.clone();
+    let solved = with_timeout(options.timeout, move || {
+        solve_parts(&mut solving, &input, &parts, &params, options.repeat);
+        solving
+    });
+    match solved {
+        Ok(solved) => *report = solved,
+        Err(timed_out) => report.error = Some(timed_out.to_string()),
+    }
+}
+
+fn solve_parts(
+    report: &mut DayReport,
+    input: &Input,
+    parts: &[Part],
+    params: &Params,
+    repeat: usize,
+) {
+    let solver =
+        days::get_solver(report.day).unwrap_or_else(|| panic!("Unknown day: {}", report.day));
+    let ((parsed, alloc_parse), time_parse) = Timing::measure(repeat, || {
+        AllocStats::measure(|| catch_panic(|| solver.parse(input)))
+    });
+    if record_timeout(report) {
+        return;
+    }
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => {
+            report.error = Some(e.to_string());
+            return;
+        }
+        Err(panic) => {
+            report.error = Some(format!("Parsing panicked: {panic}"));
+            return;
+        }
+    };
+    report.time_parse = Some(time_parse);
+    report.alloc_parse = alloc_parse;
+
+    let mut panics = Vec::new();
+    for &part in parts {
+        let ((solution, alloc), time) = Timing::measure(repeat, || {
+            AllocStats::measure(|| catch_panic(|| parsed.part(part, params)))
+        });
+        if record_timeout(report) {
+            return;
+        }
+        match solution {
+            Ok(solution) => match part {
+                Part::A => {
+                    (report.solution_a, report.time_a, report.alloc_a) =
+                        (Some(solution), Some(time), alloc)
+                }
+                Part::B => {
+                    (report.solution_b, report.time_b, report.alloc_b) =
+                        (Some(solution), Some(time), alloc)
+                }
+            },
+            Err(panic) => panics.push(format!("Part {part} panicked: {panic}")),
+        }
+    }
+    if !panics.is_empty() {
+        report.error = Some(panics.join("; "));
+    }
+}
+
+/**
+ * Whether the current thread has been cancelled by its timeout, in which case
+ * the step that was interrupted is recorded as timed out rather than as a
+ * panic, and nothing more should be run.
+ */
+fn record_timeout(report: &mut DayReport) -> bool {
+    let cancelled = is_cancelled();
+    if cancelled {
+        report.error = Some("Timed out".to_string());
+    }
+    cancelled
+}
+
+fn run_days<W: Write>(
+    reporter: &mut Reporter<W>,
+    days: &[(u8, Source, Params)],
+    options: RunOptions,
+    jobs: usize,
+    cache: Option<&ResultCache>,
+) -> Result<(), std::io::Error> {
+    map_ordered(
+        jobs,
+        days,
+        |(day, source, params)| match source {
+            Source::File(input_path) => {
+                solve_day(*day, input_path.as_deref(), params, options, cache)
+            }
+            Source::Example(number) => solve_example(*day, *number, params, options),
+        },
+        |report| reporter.report(&report),
+    )
+}
+
+/**
+ * Run the selected days, then poll their input files and run each day again
+ * when its input changes, until interrupted. Changes to the answers are shown
+ * on standard error, so they don't mix with JSON or CSV output.
+ */
+fn watch_days(
+    format: Format,
+    time: bool,
+    days: &[(u8, Source, Params)],
+    options: RunOptions,
+    jobs: usize,
+    cache: Option<&ResultCache>,
+) -> Result<(), std::io::Error> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    let paths: Vec<PathBuf> = days
+        .iter()
+        .map(|(day, source, _)| match source {
+            Source::File(Some(path)) if path == Path::new("-") => {
+                usage_error("Standard input can't be watched")
+            }
+            Source::File(path) => path.clone().unwrap_or_else(|| day_input_filename(*day)),
+            Source::Example(_) => unreachable!("--watch conflicts with --example"),
+        })
+        .collect();
+    let mut watcher = InputWatcher::new(paths);
+    let mut previous: Vec<Option<DayReport>> = vec![None; days.len()];
+    let mut changed: Vec<usize> = (0..days.len()).collect();
+
+    loop {
+        if !changed.is_empty() {
+            let mut reporter = Reporter::new(format, std::io::stdout().lock()).with_time(time);
+            reporter.begin()?;
+            map_ordered(
+                jobs,
+                &changed,
+                |i| {
+                    let (day, source, params) = &days[*i];
+                    let Source::File(input_path) = source else {
+                        unreachable!("--watch conflicts with --example");
+                    };
+                    let report = solve_day(*day, input_path.as_deref(), params, options, cache);
+                    (*i, report)
+                },
+                |(i, report)| {
+                    reporter.report(&report)?;
+                    if let Some(previous) = &previous[i] {
+                        eprintln!("Changes since the previous run of day {}:", report.day);
+                        for change in answer_changes(previous, &report) {
+                            eprintln!("  {change}");
+                        }
+                    }
+                    previous[i] = Some(report);
+                    Ok::<(), std::io::Error>(())
+                },
+            )?;
+            reporter.finish()?;
+            if let Some(cache) = cache {
+                cache.save()?;
+            }
+            eprintln!(
+                "Watching {} input file(s) for changes; press Ctrl-C to stop",
+                days.len()
+            );
+        }
+        std::thread::sleep(POLL_INTERVAL);
+        changed = watcher.changed();
+    }
+}
+
+/**
+ * Run the selected days and compare the answers with the registry. Returns the
+ * number of days that failed to run or had a wrong answer. Cached answers that
+ * disagree with the registry are flagged as such.
+ */
+fn check(matches: &ArgMatches) -> Result<usize, std::io::Error> {
+    let answers = Answers::load(Path::new(matches.value_of("answers").unwrap()))?;
+    let cache = open_cache(matches)?;
+    let options = RunOptions {
+        part: None,
+        repeat: 1,
+        timeout: parse_timeout(matches),
+    };
+
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    let mut failed_days = 0;
+    let check_report = |report: DayReport| {
+        let day = report.day;
+        if let Some(error) = &report.error {
+            println!("Day {day: >2}: ERROR {error}");
+            failed += 1;
+            failed_days += 1;
+            return Ok::<(), Infallible>(());
+        }
+
+        let failed_before = failed;
+        for (part, solution) in [(Part::A, &report.solution_a), (Part::B, &report.solution_b)] {
+            let solution = solution.as_deref().unwrap_or_default();
+            let cached = report.cached.contains(&part);
+            let note = if cached { " (cached)" } else { "" };
+            match answers.check(day, part, solution) {
+                Verdict::Pass => {
+                    println!("Day {day: >2} {part}: PASS{note}");
+                    passed += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!("Day {day: >2} {part}: FAIL{note}");
+                    println!("  - {expected}");
+                    println!("  + {solution}");
+                    if cached {
+                        println!("  The cached answer disagrees with the registry; rerun with --no-cache to recompute it");
+                    }
+                    failed += 1;
+                }
+                Verdict::Unknown => {
+                    println!("Day {day: >2} {part}: UNKNOWN ({solution}){note}");
+                    unknown += 1;
+                }
+            }
+        }
+        if failed > failed_before {
+            failed_days += 1;
+        }
+        Ok(())
+    };
+    let Ok(()) = map_ordered(
+        parse_jobs(matches),
+        &select_days(matches, &days::all_numbers()),
+        |(day, input_path)| {
+            let params = default_params(*day);
+            solve_day(
+                *day,
+                input_path.as_deref(),
+                &params,
+                options,
+                cache.as_ref(),
+            )
+        },
+        check_report,
+    );
+    if let Some(cache) = cache {
+        cache.save()?;
+    }
+
     println!();
-    println!("=== Day {day: >2} ===");
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+    Ok(failed_days)
+}
 
-    let day_func = days::get_solver(day).expect("Unknown day: {day}");
-    let lines = input_path
-        .map(get_file_lines)
-        .unwrap_or_else(|| get_file_lines(&day_input_filename(day)))?;
-    let solution = day_func(&lines);
+/**
+ * Download the inputs for the selected days. Returns the number of days that
+ * failed to download.
+ */
+fn fetch(matches: &ArgMatches) -> usize {
+    let session = match read_session(matches.value_of("session-file").map(Path::new)) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return 1;
+        }
+    };
+    let client = Client::new(matches.value_of("base-url").unwrap(), session);
 
-    println!("A: {}", solution.0);
-    println!("B: {}", solution.1);
+    // Inputs are usually fetched before the day has a solver, so any day of
+    // the calendar can be fetched.
+    let calendar: Vec<u8> = (1..=25).collect();
+    let mut failed = 0;
+    for (day, _) in select_days(matches, &calendar) {
+        let path = day_input_filename(day);
+        if path.exists() && !matches.is_present("force") {
+            println!("Day {day: >2}: {} already exists", path.display());
+            continue;
+        }
 
-    Ok(())
+        wait_for_unlock(day, |remaining| {
+            println!(
+                "Day {day: >2}: waiting {} s for the puzzle to unlock",
+                remaining.as_secs() + 1
+            );
+        });
+        match client
+            .fetch_input(day)
+            .and_then(|input| Ok(write_atomically(&path, &input)?))
+        {
+            Ok(()) => println!("Day {day: >2}: saved input to {}", path.display()),
+            Err(e) => {
+                eprintln!("Day {day: >2}: Error: {e}");
+                failed += 1;
+            }
+        }
+    }
+    failed
 }
 
-fn run_all_days() -> Result<(), std::io::Error> {
-    for day in days::all_numbers() {
-        run_day(day, None)?
+/**
+ * Submit an answer, or the computed answer if none is given, and record the
+ * outcome. Returns whether the answer was correct.
+ */
+fn submit(matches: &ArgMatches) -> Result<bool, std::io::Error> {
+    let day = matches.value_of("day").unwrap();
+    let day: u8 = day
+        .parse()
+        .ok()
+        .filter(|day| days::get_solver(*day).is_some())
+        .unwrap_or_else(|| usage_error(format!("Unknown day: {day}")));
+    let part: Part = matches.value_of("part").unwrap().parse().unwrap();
+
+    let answer = match matches.value_of("answer") {
+        Some(answer) => answer.to_string(),
+        None => {
+            let input_path = matches.value_of("input-file").map(Path::new);
+            let options = RunOptions {
+                part: Some(part),
+                repeat: 1,
+                timeout: None,
+            };
+            let report = solve_day(day, input_path, &default_params(day), options, None);
+            let solution = match part {
+                Part::A => report.solution_a,
+                Part::B => report.solution_b,
+            };
+            match (solution, report.error) {
+                (Some(solution), None) => solution,
+                (_, error) => {
+                    eprintln!("Error: {}", error.unwrap_or_default());
+                    return Ok(false);
+                }
+            }
+        }
+    };
+    if answer.is_empty() {
+        eprintln!("Day {day: >2} {part}: there is no answer to submit");
+        return Ok(false);
     }
+
+    let history_path = Path::new(matches.value_of("history").unwrap());
+    let mut history = GuessHistory::load(history_path)?;
+    if let Err(refusal) = history.check(day, part, &answer) {
+        eprintln!("Day {day: >2} {part}: not submitting {answer}: {refusal}");
+        return Ok(false);
+    }
+
+    let session = match read_session(matches.value_of("session-file").map(Path::new)) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return Ok(false);
+        }
+    };
+    let client = Client::new(matches.value_of("base-url").unwrap(), session);
+    let level = match part {
+        Part::A => 1,
+        Part::B => 2,
+    };
+    let outcome = match client.submit_answer(day, level, &answer) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return Ok(false);
+        }
+    };
+    println!("Day {day: >2} {part}: {answer} is {outcome}");
+
+    match outcome {
+        Outcome::Correct | Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow => {
+            let correct = outcome == Outcome::Correct;
+            history.record(
+                history_path,
+                Guess {
+                    day,
+                    part,
+                    answer,
+                    outcome,
+                },
+            )?;
+            Ok(correct)
+        }
+        Outcome::RateLimited { .. } | Outcome::WrongLevel | Outcome::Unrecognized(_) => Ok(false),
+    }
+}
+
+/**
+ * Create the source file for a new day and register it in the days module.
+ */
+fn new_day(matches: &ArgMatches) -> Result<(), std::io::Error> {
+    let day = matches.value_of("day").unwrap();
+    let day: u8 = day
+        .parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .unwrap_or_else(|| usage_error(format!("Invalid day: {day}")));
+    if days::get_solver(day).is_some() {
+        usage_error(format!("Day {day} already exists"));
+    }
+
+    let title = matches.value_of("title").unwrap_or_default();
+    let path = add_day(Path::new("src/days"), day, title)?;
+    println!("Created {}", path.display());
+    println!("Registered day {day} in src/days/mod.rs");
     Ok(())
 }
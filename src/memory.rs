@@ -0,0 +1,230 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+
+/**
+ * Whether the crate was built with the `alloc-stats` feature, which installs
+ * [CountingAllocator] as the global allocator.
+ */
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    /** Heap in use, counting only what this thread allocated and freed. */
+    current: i64,
+    peak: i64,
+}
+
+impl Counters {
+    const ZERO: Self = Self {
+        allocations: 0,
+        bytes: 0,
+        current: 0,
+        peak: 0,
+    };
+}
+
+/**
+ * The system allocator, counting the allocations made on each thread.
+ *
+ * Counting per thread keeps the figures of days run in parallel apart, but
+ * memory freed on another thread than the one that allocated it is not
+ * subtracted from the heap in use on the allocating thread.
+ */
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+pub struct CountingAllocator;
+
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+impl CountingAllocator {
+    fn record(allocated: usize, freed: usize) {
+        // try_with: the counters are gone while the thread shuts down
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            if allocated > 0 {
+                c.allocations += 1;
+                c.bytes += allocated as u64;
+            }
+            c.current += allocated as i64 - freed as i64;
+            c.peak = c.peak.max(c.current);
+            counters.set(c);
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/**
+ * The allocations made by one step, on the thread that ran it.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /** The number of allocations, counting each reallocation as one. */
+    pub allocations: u64,
+    /** The total size of all allocations, in bytes. */
+    pub bytes: u64,
+    /** The most heap in use at once, beyond what was in use before the step. */
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /**
+     * Run `f` and count its allocations, or return `None` instead of counts
+     * if the `alloc-stats` feature is disabled.
+     */
+    pub fn measure<T, F>(f: F) -> (T, Option<Self>)
+    where
+        F: FnOnce() -> T,
+    {
+        if !ENABLED {
+            return (f(), None);
+        }
+        let before = COUNTERS.get();
+        COUNTERS.set(Counters {
+            peak: before.current,
+            ..before
+        });
+        let result = f();
+        let after = COUNTERS.get();
+        COUNTERS.set(Counters {
+            peak: after.peak.max(before.peak),
+            ..after
+        });
+        let stats = Self {
+            allocations: after.allocations - before.allocations,
+            bytes: after.bytes - before.bytes,
+            peak: (after.peak - before.current).max(0) as u64,
+        };
+        (result, Some(stats))
+    }
+
+    /**
+     * The allocations of two steps run one after the other.
+     */
+    pub fn then(self, other: Self) -> Self {
+        Self {
+            allocations: self.allocations + other.allocations,
+            bytes: self.bytes + other.bytes,
+            peak: self.peak.max(other.peak),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllocStats;
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn measure_counts_allocations_and_peak() {
+        let (sum, stats) = AllocStats::measure(|| {
+            let small = vec![0_u8; 1000];
+            drop(small);
+            let large = vec![0_u8; 4000];
+            large.len()
+        });
+        assert_eq!(sum, 4000);
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                allocations: 2,
+                bytes: 5000,
+                peak: 4000,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn nested_measure_keeps_outer_peak() {
+        let (_, outer) = AllocStats::measure(|| {
+            let large = vec![0_u8; 4000];
+            drop(large);
+            AllocStats::measure(|| vec![0_u8; 1000].len())
+        });
+        assert_eq!(outer.map(|stats| stats.peak), Some(4000));
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn measure_without_feature_has_no_stats() {
+        assert_eq!(AllocStats::measure(|| 7), (7, None));
+    }
+
+    #[test]
+    fn then_adds_counts_and_keeps_larger_peak() {
+        let a = AllocStats {
+            allocations: 1,
+            bytes: 10,
+            peak: 8,
+        };
+        let b = AllocStats {
+            allocations: 2,
+            bytes: 5,
+            peak: 5,
+        };
+        assert_eq!(
+            a.then(b),
+            AllocStats {
+                allocations: 3,
+                bytes: 15,
+                peak: 8,
+            }
+        );
+    }
+}
@@ -0,0 +1,133 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::thread;
+
+/**
+ * The number of worker threads to use for a `--jobs` value, where 0 means one
+ * per available CPU.
+ */
+pub fn worker_count(jobs: usize) -> usize {
+    if jobs == 0 {
+        thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+    } else {
+        jobs
+    }
+}
+
+/**
+ * Apply `f` to each of `items` on up to `jobs` worker threads, and pass the
+ * results to `on_result` in the order of `items`. Each result is passed on as
+ * soon as it and all results before it are done.
+ *
+ * If `on_result` returns an error, no more items are started and the error is
+ * returned once the items already in progress are done.
+ */
+pub fn map_ordered<T, R, E, F, G>(jobs: usize, items: &[T], f: F, mut on_result: G) -> Result<(), E>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    G: FnMut(R) -> Result<(), E>,
+{
+    if jobs <= 1 {
+        return items.iter().try_for_each(|item| on_result(f(item)));
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() || tx.send((i, f(&items[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut done = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&done) {
+                done += 1;
+                if let Err(e) = on_result(result) {
+                    next.store(items.len(), Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::map_ordered;
+    use std::time::Duration;
+
+    #[test]
+    fn results_arrive_in_item_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut results = Vec::new();
+        map_ordered(
+            4,
+            &items,
+            |i| {
+                std::thread::sleep(Duration::from_millis((20 - i) % 7));
+                i * i
+            },
+            |r| {
+                results.push(r);
+                Ok::<(), ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(results, items.iter().map(|i| i * i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn error_stops_after_items_in_progress() {
+        let items: Vec<u64> = (0..100).collect();
+        let mut results = Vec::new();
+        let outcome = map_ordered(
+            2,
+            &items,
+            |i| *i,
+            |r| {
+                results.push(r);
+                if r == 3 {
+                    Err(r)
+                } else {
+                    Ok(())
+                }
+            },
+        );
+        assert_eq!(outcome, Err(3));
+        assert_eq!(results, vec![0, 1, 2, 3]);
+    }
+}
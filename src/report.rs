@@ -0,0 +1,781 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::memory::AllocStats;
+use adventofcode_2024::cancel;
+use adventofcode_2024::common::Normalization;
+use adventofcode_2024::solver::Part;
+
+/**
+ * Output format of the CLI runner.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("Unknown output format: {s}")),
+        }
+    }
+}
+
+/**
+ * Durations of repeated runs of the same step.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Timing {
+    samples: Vec<Duration>,
+}
+
+impl Timing {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "Timing needs at least one sample");
+        samples.sort();
+        Self { samples }
+    }
+
+    /**
+     * Run `f` `repeat` times (at least once), returning the last result and
+     * the duration of each run. The runs stop early once the current thread
+     * is cancelled.
+     */
+    pub fn measure<T, F>(repeat: usize, mut f: F) -> (T, Self)
+    where
+        F: FnMut() -> T,
+    {
+        let mut samples = Vec::with_capacity(repeat.max(1));
+        let mut result = None;
+        for _ in 0..repeat.max(1) {
+            let start = Instant::now();
+            result = Some(f());
+            samples.push(start.elapsed());
+            if cancel::is_cancelled() {
+                break;
+            }
+        }
+        (result.unwrap(), Self::new(samples))
+    }
+
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        let mid = self.samples.len() / 2;
+        if self.samples.len().is_multiple_of(2) {
+            (self.samples[mid - 1] + self.samples[mid]) / 2
+        } else {
+            self.samples[mid]
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+/**
+ * The outcome of running one day. Answers and timings are `None` for parts
+ * that were not run, or not reached because of an error.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct DayReport {
+    pub day: u8,
+    pub input_path: PathBuf,
+    /** The 1-based number of the example, if the input is an example. */
+    pub example: Option<usize>,
+    /** What was changed in the input to normalize it. */
+    pub normalization: Normalization,
+    pub solution_a: Option<String>,
+    pub solution_b: Option<String>,
+    pub time_parse: Option<Timing>,
+    pub time_a: Option<Timing>,
+    pub time_b: Option<Timing>,
+    /** Allocations of each step, if built with the `alloc-stats` feature. */
+    pub alloc_parse: Option<AllocStats>,
+    pub alloc_a: Option<AllocStats>,
+    pub alloc_b: Option<AllocStats>,
+    /** Parts whose answers and timings were served from the result cache. */
+    pub cached: Vec<Part>,
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    /**
+     * Sum of the median durations of the steps that were run.
+     */
+    pub fn total_time(&self) -> Duration {
+        [&self.time_parse, &self.time_a, &self.time_b]
+            .into_iter()
+            .flatten()
+            .map(Timing::median)
+            .sum()
+    }
+
+    /**
+     * The allocations of all steps that were run, if counted.
+     */
+    pub fn total_alloc(&self) -> Option<AllocStats> {
+        [self.alloc_parse, self.alloc_a, self.alloc_b]
+            .into_iter()
+            .flatten()
+            .reduce(AllocStats::then)
+    }
+}
+
+/**
+ * Writes [DayReport]s in the chosen [Format]. JSON output is an array with one
+ * object per day, and CSV output starts with a header row, so [Reporter::begin]
+ * and [Reporter::finish] must bracket the calls to [Reporter::report].
+ *
+ * JSON and CSV output always include timings, as medians in milliseconds. Text
+ * output includes them only if enabled with [Reporter::with_time], along with a
+ * grand total when more than one day is reported. If days were run in
+ * parallel, [Reporter::set_wall_time] adds the wall time of the whole run to
+ * the total, next to the time summed over all days.
+ *
+ * If allocations were counted, text output also lists the steps that allocated
+ * the most bytes.
+ *
+ * A day fails if it has an error. Text output ends with a summary listing the
+ * failed days, if there are any.
+ */
+pub struct Reporter<W> {
+    format: Format,
+    out: W,
+    time: bool,
+    reported: usize,
+    total_time: Duration,
+    wall_time: Option<Duration>,
+    allocs: Vec<(u8, &'static str, AllocStats)>,
+    failed: Vec<(u8, String)>,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Self {
+            format,
+            out,
+            time: false,
+            reported: 0,
+            total_time: Duration::ZERO,
+            wall_time: None,
+            allocs: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    pub fn with_time(self, time: bool) -> Self {
+        Self { time, ..self }
+    }
+
+    pub fn set_wall_time(&mut self, wall_time: Duration) {
+        self.wall_time = Some(wall_time);
+    }
+
+    /**
+     * The number of days reported so far that failed.
+     */
+    pub fn failed(&self) -> usize {
+        self.failed.len()
+    }
+
+    pub fn begin(&mut self) -> std::io::Result<()> {
+        match self.format {
+            Format::Text => Ok(()),
+            Format::Json => write!(self.out, "["),
+            Format::Csv => writeln!(
+                self.out,
+                "day,input,answer_a,answer_b,parse_ms,a_ms,b_ms,error"
+            ),
+        }
+    }
+
+    pub fn report(&mut self, report: &DayReport) -> std::io::Result<()> {
+        match self.format {
+            Format::Text => self.report_text(report)?,
+            Format::Json => self.report_json(report)?,
+            Format::Csv => self.report_csv(report)?,
+        }
+        self.reported += 1;
+        self.total_time += report.total_time();
+        for (step, alloc) in alloc_steps(report) {
+            self.allocs.push((report.day, step, alloc));
+        }
+        if let Some(error) = &report.error {
+            self.failed.push((report.day, error.clone()));
+        }
+        self.out.flush()
+    }
+
+    pub fn finish(&mut self) -> std::io::Result<()> {
+        match self.format {
+            Format::Text => {
+                if let Some(wall_time) = self.wall_time {
+                    writeln!(self.out)?;
+                    writeln!(self.out, "=== Total ===")?;
+                    writeln!(self.out, "CPU time:  {} ms", millis(self.total_time))?;
+                    writeln!(self.out, "Wall time: {} ms", millis(wall_time))?;
+                } else if self.time && self.reported > 1 {
+                    writeln!(self.out)?;
+                    writeln!(self.out, "=== Total ===")?;
+                    writeln!(self.out, "Time: {} ms", millis(self.total_time))?;
+                }
+                if !self.allocs.is_empty() {
+                    self.allocs
+                        .sort_by_key(|(_, _, alloc)| std::cmp::Reverse(alloc.bytes));
+                    writeln!(self.out)?;
+                    writeln!(self.out, "=== Most allocated ===")?;
+                    for (day, step, alloc) in self.allocs.iter().take(MOST_ALLOCATED) {
+                        writeln!(
+                            self.out,
+                            "Day {: >2} {: <6} {}",
+                            day,
+                            format!("{step}:"),
+                            alloc_summary(alloc)
+                        )?;
+                    }
+                }
+                if !self.failed.is_empty() {
+                    writeln!(self.out)?;
+                    writeln!(self.out, "=== Summary ===")?;
+                    for (day, error) in &self.failed {
+                        writeln!(self.out, "FAILED Day {: >2}: {}", day, error)?;
+                    }
+                    writeln!(
+                        self.out,
+                        "{} of {} days failed",
+                        self.failed.len(),
+                        self.reported
+                    )?;
+                }
+                Ok(())
+            }
+            Format::Csv => Ok(()),
+            Format::Json => {
+                if self.reported > 0 {
+                    writeln!(self.out)?;
+                }
+                writeln!(self.out, "]")
+            }
+        }
+    }
+
+    fn report_text(&mut self, report: &DayReport) -> std::io::Result<()> {
+        writeln!(self.out)?;
+        match report.example {
+            Some(example) => writeln!(
+                self.out,
+                "=== Day {: >2}, example {} ===",
+                report.day, example
+            )?,
+            None => writeln!(self.out, "=== Day {: >2} ===", report.day)?,
+        }
+        if !report.normalization.is_empty() {
+            writeln!(self.out, "Normalized input: {}", report.normalization)?;
+        }
+        for (part, solution) in [(Part::A, &report.solution_a), (Part::B, &report.solution_b)] {
+            if let Some(solution) = solution {
+                write!(self.out, "{part}: {solution}")?;
+                if report.cached.contains(&part) {
+                    write!(self.out, " (cached)")?;
+                }
+                writeln!(self.out)?;
+            }
+        }
+        if self.time {
+            for (name, timing) in [
+                ("Parse", &report.time_parse),
+                ("A", &report.time_a),
+                ("B", &report.time_b),
+            ] {
+                if let Some(timing) = timing {
+                    write!(
+                        self.out,
+                        "{: <6} {} ms",
+                        format!("{name}:"),
+                        millis(timing.median())
+                    )?;
+                    if timing.runs() > 1 {
+                        write!(
+                            self.out,
+                            " (median of {} runs; min {} ms, max {} ms)",
+                            timing.runs(),
+                            millis(timing.min()),
+                            millis(timing.max())
+                        )?;
+                    }
+                    writeln!(self.out)?;
+                }
+            }
+            writeln!(self.out, "Total: {} ms", millis(report.total_time()))?;
+        }
+        for (step, alloc) in alloc_steps(report) {
+            writeln!(
+                self.out,
+                "{: <12} {}",
+                format!("Alloc {step}:"),
+                alloc_summary(&alloc)
+            )?;
+        }
+        if alloc_steps(report).count() > 1 {
+            if let Some(total) = report.total_alloc() {
+                writeln!(self.out, "Alloc total: {}", alloc_summary(&total))?;
+            }
+        }
+        if let Some(error) = &report.error {
+            eprintln!("Error: {error}");
+        }
+        Ok(())
+    }
+
+    fn report_json(&mut self, report: &DayReport) -> std::io::Result<()> {
+        if self.reported > 0 {
+            write!(self.out, ",")?;
+        }
+        write!(
+            self.out,
+            "\n  {{\"day\": {}, \"input\": {}, \"answers\": {{\"a\": {}, \"b\": {}}}, \"time_ms\": {}, \"time_ms_min\": {}, \"time_ms_max\": {}, \"runs\": {}, \"alloc\": {}, \"normalized\": {{\"bom\": {}, \"crlf\": {}, \"trailing_whitespace\": {}}}, \"cached\": {{\"a\": {}, \"b\": {}}}, \"error\": {}}}",
+            report.day,
+            json_string(&report.input_path.to_string_lossy()),
+            report.solution_a.as_deref().map(json_string).unwrap_or_else(null),
+            report.solution_b.as_deref().map(json_string).unwrap_or_else(null),
+            json_timings(report, Timing::median),
+            json_timings(report, Timing::min),
+            json_timings(report, Timing::max),
+            [&report.time_parse, &report.time_a, &report.time_b]
+                .into_iter()
+                .flatten()
+                .map(Timing::runs)
+                .max()
+                .unwrap_or(0),
+            json_allocs(report),
+            report.normalization.bom,
+            report.normalization.crlf,
+            report.normalization.trailing_whitespace,
+            report.cached.contains(&Part::A),
+            report.cached.contains(&Part::B),
+            report.error.as_deref().map(json_string).unwrap_or_else(null),
+        )
+    }
+
+    fn report_csv(&mut self, report: &DayReport) -> std::io::Result<()> {
+        writeln!(
+            self.out,
+            "{},{},{},{},{},{},{},{}",
+            report.day,
+            csv_field(&report.input_path.to_string_lossy()),
+            report
+                .solution_a
+                .as_deref()
+                .map(csv_field)
+                .unwrap_or_default(),
+            report
+                .solution_b
+                .as_deref()
+                .map(csv_field)
+                .unwrap_or_default(),
+            csv_timing(&report.time_parse),
+            csv_timing(&report.time_a),
+            csv_timing(&report.time_b),
+            report.error.as_deref().map(csv_field).unwrap_or_default(),
+        )
+    }
+}
+
+fn null() -> String {
+    "null".to_string()
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+fn json_timings(report: &DayReport, stat: fn(&Timing) -> Duration) -> String {
+    let json_timing = |timing: &Option<Timing>| {
+        timing
+            .as_ref()
+            .map(|t| millis(stat(t)))
+            .unwrap_or_else(null)
+    };
+    format!(
+        "{{\"parse\": {}, \"a\": {}, \"b\": {}}}",
+        json_timing(&report.time_parse),
+        json_timing(&report.time_a),
+        json_timing(&report.time_b),
+    )
+}
+
+/** The number of steps listed in the summary of the most allocated bytes. */
+const MOST_ALLOCATED: usize = 5;
+
+fn alloc_steps(report: &DayReport) -> impl Iterator<Item = (&'static str, AllocStats)> {
+    [
+        ("parse", report.alloc_parse),
+        ("A", report.alloc_a),
+        ("B", report.alloc_b),
+    ]
+    .into_iter()
+    .filter_map(|(step, alloc)| Some((step, alloc?)))
+}
+
+fn alloc_summary(alloc: &AllocStats) -> String {
+    format!(
+        "{} allocations, {} allocated, {} peak",
+        alloc.allocations,
+        byte_size(alloc.bytes),
+        byte_size(alloc.peak)
+    )
+}
+
+fn byte_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn json_allocs(report: &DayReport) -> String {
+    let json_alloc = |alloc: &Option<AllocStats>| {
+        alloc
+            .map(|alloc| {
+                format!(
+                    "{{\"allocations\": {}, \"bytes\": {}, \"peak_bytes\": {}}}",
+                    alloc.allocations, alloc.bytes, alloc.peak
+                )
+            })
+            .unwrap_or_else(null)
+    };
+    format!(
+        "{{\"parse\": {}, \"a\": {}, \"b\": {}, \"total\": {}}}",
+        json_alloc(&report.alloc_parse),
+        json_alloc(&report.alloc_a),
+        json_alloc(&report.alloc_b),
+        json_alloc(&report.total_alloc()),
+    )
+}
+
+fn csv_timing(timing: &Option<Timing>) -> String {
+    timing
+        .as_ref()
+        .map(|t| millis(t.median()))
+        .unwrap_or_default()
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DayReport;
+    use super::Format;
+    use super::Reporter;
+    use super::Timing;
+    use crate::memory::AllocStats;
+    use adventofcode_2024::cancel;
+    use adventofcode_2024::cancel::CancelToken;
+    use adventofcode_2024::common::Normalization;
+    use adventofcode_2024::solver::Part;
+    use std::time::Duration;
+
+    fn run(format: Format, reports: &[DayReport]) -> String {
+        run_with_time(format, false, reports)
+    }
+
+    fn run_with_time(format: Format, time: bool, reports: &[DayReport]) -> String {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(format, &mut out).with_time(time);
+        reporter.begin().unwrap();
+        for report in reports {
+            reporter.report(report).unwrap();
+        }
+        reporter.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn example() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 23,
+                input_path: "inputs/day23.in".into(),
+                example: None,
+                normalization: Normalization::default(),
+                solution_a: Some("7".to_string()),
+                solution_b: Some("co,de,ka,ta".to_string()),
+                time_parse: Some(Timing::new(vec![Duration::from_micros(1500)])),
+                time_a: Some(Timing::new(vec![
+                    Duration::from_millis(4),
+                    Duration::from_millis(1),
+                    Duration::from_millis(2),
+                ])),
+                time_b: Some(Timing::new(vec![Duration::from_millis(3)])),
+                alloc_parse: None,
+                alloc_a: None,
+                alloc_b: None,
+                cached: Vec::new(),
+                error: None,
+            },
+            DayReport {
+                day: 24,
+                input_path: "-".into(),
+                error: Some(
+                    "Day 24, line 1, column 1: expected \"x\", found end of input".to_string(),
+                ),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn json_has_one_object_per_day() {
+        assert_eq!(
+            run(Format::Json, &example()),
+            r#"[
+  {"day": 23, "input": "inputs/day23.in", "answers": {"a": "7", "b": "co,de,ka,ta"}, "time_ms": {"parse": 1.500, "a": 2.000, "b": 3.000}, "time_ms_min": {"parse": 1.500, "a": 1.000, "b": 3.000}, "time_ms_max": {"parse": 1.500, "a": 4.000, "b": 3.000}, "runs": 3, "alloc": {"parse": null, "a": null, "b": null, "total": null}, "normalized": {"bom": false, "crlf": 0, "trailing_whitespace": 0}, "cached": {"a": false, "b": false}, "error": null},
+  {"day": 24, "input": "-", "answers": {"a": null, "b": null}, "time_ms": {"parse": null, "a": null, "b": null}, "time_ms_min": {"parse": null, "a": null, "b": null}, "time_ms_max": {"parse": null, "a": null, "b": null}, "runs": 0, "alloc": {"parse": null, "a": null, "b": null, "total": null}, "normalized": {"bom": false, "crlf": 0, "trailing_whitespace": 0}, "cached": {"a": false, "b": false}, "error": "Day 24, line 1, column 1: expected \"x\", found end of input"}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn json_without_days_is_empty_array() {
+        assert_eq!(run(Format::Json, &[]), "[]\n");
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_commas_and_quotes() {
+        assert_eq!(
+            run(Format::Csv, &example()),
+            r#"day,input,answer_a,answer_b,parse_ms,a_ms,b_ms,error
+23,inputs/day23.in,7,"co,de,ka,ta",1.500,2.000,3.000,
+24,-,,,,,,"Day 24, line 1, column 1: expected ""x"", found end of input"
+"#
+        );
+    }
+
+    #[test]
+    fn text_reports_timings_and_grand_total() {
+        assert_eq!(
+            run_with_time(Format::Text, true, &example()),
+            r#"
+=== Day 23 ===
+A: 7
+B: co,de,ka,ta
+Parse: 1.500 ms
+A:     2.000 ms (median of 3 runs; min 1.000 ms, max 4.000 ms)
+B:     3.000 ms
+Total: 6.500 ms
+
+=== Day 24 ===
+Total: 0.000 ms
+
+=== Total ===
+Time: 6.500 ms
+
+=== Summary ===
+FAILED Day 24: Day 24, line 1, column 1: expected "x", found end of input
+1 of 2 days failed
+"#
+        );
+    }
+
+    #[test]
+    fn text_omits_timings_by_default() {
+        assert_eq!(
+            run(Format::Text, &example()[..1]),
+            "\n=== Day 23 ===\nA: 7\nB: co,de,ka,ta\n"
+        );
+    }
+
+    #[test]
+    fn text_reports_normalized_input() {
+        let report = DayReport {
+            normalization: Normalization {
+                bom: false,
+                crlf: 140,
+                trailing_whitespace: 1,
+            },
+            ..example()[0].clone()
+        };
+        assert_eq!(
+            run(Format::Text, &[report]),
+            "\n=== Day 23 ===\nNormalized input: converted 140 CRLF line endings, trimmed trailing whitespace on 1 line\nA: 7\nB: co,de,ka,ta\n"
+        );
+    }
+
+    #[test]
+    fn text_reports_allocations_and_most_allocated_steps() {
+        let alloc = |allocations, bytes, peak| {
+            Some(AllocStats {
+                allocations,
+                bytes,
+                peak,
+            })
+        };
+        let reports = [
+            DayReport {
+                alloc_parse: alloc(3, 300, 300),
+                alloc_a: alloc(1, 1024, 0),
+                alloc_b: alloc(40000, 3 << 30, 5 << 20),
+                ..example()[0].clone()
+            },
+            DayReport {
+                alloc_parse: alloc(2, 2000, 1536),
+                ..example()[1].clone()
+            },
+        ];
+        assert_eq!(
+            run(Format::Text, &reports),
+            r#"
+=== Day 23 ===
+A: 7
+B: co,de,ka,ta
+Alloc parse: 3 allocations, 300 B allocated, 300 B peak
+Alloc A:     1 allocations, 1.0 KiB allocated, 0 B peak
+Alloc B:     40000 allocations, 3.0 GiB allocated, 5.0 MiB peak
+Alloc total: 40004 allocations, 3.0 GiB allocated, 5.0 MiB peak
+
+=== Day 24 ===
+Alloc parse: 2 allocations, 2.0 KiB allocated, 1.5 KiB peak
+
+=== Most allocated ===
+Day 23 B:     40000 allocations, 3.0 GiB allocated, 5.0 MiB peak
+Day 24 parse: 2 allocations, 2.0 KiB allocated, 1.5 KiB peak
+Day 23 A:     1 allocations, 1.0 KiB allocated, 0 B peak
+Day 23 parse: 3 allocations, 300 B allocated, 300 B peak
+
+=== Summary ===
+FAILED Day 24: Day 24, line 1, column 1: expected "x", found end of input
+1 of 2 days failed
+"#
+        );
+    }
+
+    #[test]
+    fn text_marks_cached_answers() {
+        let report = DayReport {
+            cached: vec![Part::B],
+            ..example()[0].clone()
+        };
+        assert_eq!(
+            run(Format::Text, &[report]),
+            "\n=== Day 23 ===\nA: 7\nB: co,de,ka,ta (cached)\n"
+        );
+    }
+
+    #[test]
+    fn median_of_even_number_of_samples_is_mean_of_middle_two() {
+        let timing = Timing::new(vec![
+            Duration::from_millis(8),
+            Duration::from_millis(2),
+            Duration::from_millis(4),
+            Duration::from_millis(1),
+        ]);
+        assert_eq!(timing.min(), Duration::from_millis(1));
+        assert_eq!(timing.median(), Duration::from_millis(3));
+        assert_eq!(timing.max(), Duration::from_millis(8));
+    }
+
+    #[test]
+    fn measure_stops_when_cancelled() {
+        let token = CancelToken::new();
+        let mut runs = 0;
+        let (result, timing) = cancel::with_token(&token, || {
+            Timing::measure(5, || {
+                runs += 1;
+                if runs == 2 {
+                    token.cancel();
+                }
+                runs
+            })
+        });
+        assert_eq!(result, 2);
+        assert_eq!(timing.runs(), 2);
+    }
+
+    #[test]
+    fn text_reports_wall_time_next_to_cpu_time() {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(Format::Text, &mut out);
+        reporter.begin().unwrap();
+        reporter.report(&example()[0]).unwrap();
+        reporter.set_wall_time(Duration::from_millis(4));
+        reporter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"
+=== Day 23 ===
+A: 7
+B: co,de,ka,ta
+
+=== Total ===
+CPU time:  6.500 ms
+Wall time: 4.000 ms
+"#
+        );
+    }
+}
@@ -0,0 +1,293 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+const TEMPLATE: &str = r#"// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    common::ParseError,
+    input::Input,
+    solver::{Example, Params, Solver},
+};
+
+fn solve_a(_lines: &[&str]) -> usize {
+    todo!("Day {{DAY}} part A")
+}
+
+fn solve_b(_lines: &[&str]) -> usize {
+    todo!("Day {{DAY}} part B")
+}
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "
+",
+    part_a: None,
+    part_b: None,
+    params: &[],
+}];
+
+pub struct Day;
+
+impl Solver for Day {
+    const DAY: u8 = {{DAY}};
+    const TITLE: &'static str = {{TITLE}};
+    const EXAMPLES: &'static [Example] = EXAMPLES;
+    type Parsed<'input> = Vec<&'input str>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError> {
+        Ok(input.lines().filter(|line| !line.is_empty()).collect())
+    }
+
+    fn part_a(&self, lines: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_a(lines).to_string()
+    }
+
+    fn part_b(&self, lines: &Self::Parsed<'_>, _params: &Params) -> String {
+        solve_b(lines).to_string()
+    }
+}
+"#;
+
+/**
+ * Source code for a new `day` titled `title`, with stubs for parsing, solving
+ * and the examples.
+ */
+pub fn day_source(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{TITLE}}", &format!("{title:?}"))
+}
+
+/**
+ * Add `day` to the module declarations and the `with_days!` list in `mod_rs`,
+ * the source of the `days` module, keeping both in order.
+ */
+pub fn register_day(mod_rs: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{day:02}");
+    let mut lines: Vec<String> = mod_rs.lines().map(str::to_string).collect();
+    insert_entry(&mut lines, &name, "module declarations", |line| {
+        line.strip_prefix("pub mod ")?
+            .strip_suffix(';')
+            .filter(|rest| is_day_name(rest))
+    })?;
+    insert_list_entry(&mut lines, &name)?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/**
+ * Insert `name` into the arguments of the `$callback!` invocation in the
+ * `with_days!` macro, wrapped the way rustfmt wraps them.
+ */
+fn insert_list_entry(lines: &mut Vec<String>, name: &str) -> Result<(), String> {
+    const MAX_WIDTH: usize = 100;
+
+    let start = lines
+        .iter()
+        .position(|line| line.trim_end().ends_with("$callback!("))
+        .ok_or("Found no with_days! list in the days module")?
+        + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.trim() == ");")
+        .map(|len| start + len)
+        .filter(|end| *end > start)
+        .ok_or("Found no with_days! list in the days module")?;
+
+    let indent: String = lines[start].chars().take_while(|c| *c == ' ').collect();
+    let mut names: Vec<&str> = lines[start..end]
+        .iter()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if names.contains(&name) {
+        return Err(format!("{name} is already in the with_days! list"));
+    }
+    names.push(name);
+    names.sort();
+
+    let mut wrapped: Vec<String> = Vec::new();
+    for name in names {
+        match wrapped.last_mut() {
+            Some(line) if line.len() + name.len() + 2 <= MAX_WIDTH => {
+                line.push(' ');
+                line.push_str(name);
+                line.push(',');
+            }
+            _ => wrapped.push(format!("{indent}{name},")),
+        }
+    }
+    lines.splice(start..end, wrapped);
+    Ok(())
+}
+
+/**
+ * Insert a line for `name` among the `lines` from which `entry` extracts a day
+ * name, after the last one that sorts before it and in the same form as the
+ * first one.
+ */
+fn insert_entry<F>(lines: &mut Vec<String>, name: &str, what: &str, entry: F) -> Result<(), String>
+where
+    F: Fn(&str) -> Option<&str>,
+{
+    let entries: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, entry(line)?)))
+        .collect();
+    let Some((first, first_name)) = entries.first() else {
+        return Err(format!("Found no {what} in the days module"));
+    };
+    if entries.iter().any(|(_, existing)| *existing == name) {
+        return Err(format!("{name} is already in the {what}"));
+    }
+    let new_line = lines[*first].replacen(first_name, name, 1);
+    let at = entries
+        .iter()
+        .find(|(_, existing)| *existing > name)
+        .map(|(i, _)| *i)
+        .unwrap_or_else(|| entries[entries.len() - 1].0 + 1);
+    lines.insert(at, new_line);
+    Ok(())
+}
+
+fn is_day_name(s: &str) -> bool {
+    s.strip_prefix("day")
+        .is_some_and(|num| num.len() == 2 && num.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/**
+ * Create the source file for `day` titled `title` in `days_dir` and register
+ * it in the `mod.rs` there. Returns the path of the new file.
+ */
+pub fn add_day(days_dir: &Path, day: u8, title: &str) -> Result<PathBuf, std::io::Error> {
+    let mod_path = days_dir.join("mod.rs");
+    let mod_rs = std::fs::read_to_string(&mod_path)?;
+    let mod_rs = register_day(&mod_rs, day)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    let path = days_dir.join(format!("day{day:02}.rs"));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?
+        .write_all(day_source(day, title).as_bytes())?;
+    std::fs::write(&mod_path, mod_rs)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::solver::DaySolver;
+
+pub mod day01;
+pub mod day03;
+
+#[macro_export]
+macro_rules! with_days {
+    ($callback:ident) => {
+        $callback!(
+            day01, day03,
+        );
+    };
+}
+";
+
+    #[test]
+    fn day_source_declares_day() {
+        let source = day_source(7, "Bridge \"Repair\"");
+        assert!(source.contains("const DAY: u8 = 7;"));
+        assert!(source.contains(r#"const TITLE: &'static str = "Bridge \"Repair\"";"#));
+        assert!(source.contains("todo!(\"Day 7 part A\")"));
+        assert!(!source.contains("{{DAY}}"));
+        assert!(!source.contains("{{TITLE}}"));
+    }
+
+    #[test]
+    fn register_day_in_order() {
+        assert_eq!(
+            register_day(MOD_RS, 2).unwrap(),
+            MOD_RS
+                .replace("pub mod day01;\n", "pub mod day01;\npub mod day02;\n")
+                .replace("day01, day03", "day01, day02, day03")
+        );
+        assert_eq!(
+            register_day(MOD_RS, 4).unwrap(),
+            MOD_RS
+                .replace("pub mod day03;\n", "pub mod day03;\npub mod day04;\n")
+                .replace("day01, day03", "day01, day03, day04")
+        );
+    }
+
+    #[test]
+    fn register_day_wraps_list() {
+        let mod_rs = (1..=12).fold(MOD_RS.to_string(), |mod_rs, day| {
+            register_day(&mod_rs, day * 2).unwrap()
+        });
+        assert!(mod_rs.contains(
+            "
+            day01, day02, day03, day04, day06, day08, day10, day12, day14, day16, day18, day20,
+            day22, day24,
+"
+        ));
+    }
+
+    #[test]
+    fn register_day_rejects_existing() {
+        assert!(register_day(MOD_RS, 3).is_err());
+        assert!(register_day("pub mod day01;\n", 2).is_err());
+    }
+
+    #[test]
+    fn add_day_writes_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("mod.rs"), MOD_RS).unwrap();
+
+        let path = add_day(&dir, 2, "Red-Nosed Reports").unwrap();
+        assert_eq!(path, dir.join("day02.rs"));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            day_source(2, "Red-Nosed Reports")
+        );
+        assert!(std::fs::read_to_string(dir.join("mod.rs"))
+            .unwrap()
+            .contains("pub mod day02;"));
+        assert!(add_day(&dir, 2, "").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -14,33 +14,98 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+/*!
+ * A* search. The search space is described by implementing [State] for the
+ * type of the search states, and searched from an initial state with [astar],
+ * [astar_all_best] or [astar_optimize].
+ *
+ * The searches keep track of how each state was reached, by the
+ * [State::DuplicationKey] of its predecessor, so the path to a goal can be
+ * recovered without the states having to link to their predecessors.
+ */
+
 use std::collections::hash_map::Entry;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::cancel;
 
+/**
+ * A state of an A* search: a node of the search graph together with the cost
+ * of the path that reached it.
+ */
 pub trait State
 where
     Self: PartialEq,
     Self: Eq,
 {
-    type DuplicationKey: Eq + std::hash::Hash;
+    /**
+     * Identifies the node of the state, so that states reaching the same node
+     * by different paths can be recognized.
+     */
+    type DuplicationKey: Clone + Eq + std::hash::Hash;
+    /** The cost of a path. Lower is better. */
     type Value: Ord;
     type NewStates: Iterator<Item = Self>;
 
+    /** The cost of the path to this state. */
     fn value(&self) -> Self::Value;
+
+    /**
+     * The cost of the path to this state plus a lower bound of the remaining
+     * cost to a goal. The bound must never overestimate, and is zero at a
+     * goal.
+     */
     fn estimate(&self) -> Self::Value;
+
     fn duplication_key(&self) -> Self::DuplicationKey;
+
+    /** The states one step onward from this one. */
     fn generate_moves(self) -> Self::NewStates;
 
+    /**
+     * Whether this state is a goal. By default, a state is a goal if its
+     * estimate equals its value, that is if no cost remains.
+     */
     fn finished(&self) -> bool {
         self.estimate() == self.value()
     }
+
+    /**
+     * Orders states with equal estimates: of two such states, the one with
+     * the greater tie-break is expanded first, for example to prefer deeper
+     * states. States that tie here too are expanded in the order they were
+     * generated. By default, all states have the same tie-break.
+     */
+    fn tie_break(&self) -> i64 {
+        0
+    }
 }
 
-#[derive(Eq, PartialEq)]
-struct StateOrd<S>(S)
+/**
+ * A queued state, with the number of states queued before it. Queued states
+ * compare by when they will be popped, so they are only equal if they have the
+ * same order.
+ */
+struct StateOrd<S>
 where
-    S: State;
+    S: State,
+{
+    state: S,
+    order: usize,
+}
+
+impl<S> PartialEq for StateOrd<S>
+where
+    S: State,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.cmp(rhs) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<S> Eq for StateOrd<S> where S: State {}
 
 impl<S> PartialOrd for StateOrd<S>
 where
@@ -54,42 +119,157 @@ where
 impl<S> Ord for StateOrd<S>
 where
     S: State,
-    S: PartialEq,
-    S: Eq,
 {
     fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
-        rhs.0.estimate().cmp(&self.0.estimate())
+        rhs.state
+            .estimate()
+            .cmp(&self.state.estimate())
+            .then_with(|| self.state.tie_break().cmp(&rhs.state.tie_break()))
+            .then_with(|| rhs.order.cmp(&self.order))
     }
 }
 
-pub fn astar<S>(initial_state: S) -> Option<S>
+/**
+ * The priority queue of a search, which pops the state with the lowest
+ * estimate first, breaking ties as described by [State::tie_break]. The same
+ * states pushed in the same order are always popped in the same order.
+ */
+struct Queue<S>
 where
     S: State,
 {
-    let mut queue: BinaryHeap<StateOrd<S>> = BinaryHeap::new();
+    heap: BinaryHeap<StateOrd<S>>,
+    pushed: usize,
+}
+
+impl<S> Queue<S>
+where
+    S: State,
+{
+    fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            pushed: 0,
+        }
+    }
+
+    fn push(&mut self, state: S) {
+        self.heap.push(StateOrd {
+            state,
+            order: self.pushed,
+        });
+        self.pushed += 1;
+    }
+
+    /**
+     * Pop the next state to expand, or unwind through [cancel::check] if the
+     * search has been cancelled.
+     */
+    fn pop(&mut self) -> Option<S> {
+        cancel::check();
+        self.heap.pop().map(|StateOrd { state, .. }| state)
+    }
+}
+
+/**
+ * The goal state found by [astar], and the path that reached it.
+ */
+pub struct SearchResult<S>
+where
+    S: State,
+{
+    pub state: S,
+    /**
+     * The duplication keys of the states along the path, from the initial
+     * state to [Self::state].
+     */
+    pub path: Vec<S::DuplicationKey>,
+}
+
+/**
+ * The goal states found by [astar_all_best], and every optimal way to reach
+ * them.
+ */
+pub struct BestPaths<S>
+where
+    S: State,
+{
+    pub goals: Vec<S>,
+    /**
+     * For each duplication key reached, the duplication keys of the states
+     * that lead to it by a path with the lowest value. Together they form a
+     * directed acyclic graph from the goals back to the initial state.
+     */
+    pub predecessors: HashMap<S::DuplicationKey, Vec<S::DuplicationKey>>,
+}
+
+impl<S> BestPaths<S>
+where
+    S: State,
+{
+    /**
+     * The duplication keys of every state on some path with the lowest value
+     * to one of the goals.
+     */
+    pub fn keys(&self) -> HashSet<&S::DuplicationKey> {
+        let mut keys = HashSet::new();
+        let mut stack: Vec<&S::DuplicationKey> = Vec::new();
+        for goal in &self.goals {
+            let key = self
+                .predecessors
+                .get_key_value(&goal.duplication_key())
+                .map(|(key, _)| key);
+            stack.extend(key);
+        }
+        while let Some(key) = stack.pop() {
+            if keys.insert(key) {
+                stack.extend(self.predecessors.get(key).into_iter().flatten());
+            }
+        }
+        keys
+    }
+}
+
+/**
+ * Search from `initial_state` for a goal state with the lowest value, and
+ * return it with its path, or `None` if no goal can be reached.
+ */
+pub fn astar<S>(initial_state: S) -> Option<SearchResult<S>>
+where
+    S: State,
+{
+    let mut queue = Queue::new();
     let mut visited: HashMap<S::DuplicationKey, S::Value> = HashMap::new();
+    let mut predecessors: HashMap<S::DuplicationKey, S::DuplicationKey> = HashMap::new();
 
-    queue.push(StateOrd(initial_state));
+    visited.insert(initial_state.duplication_key(), initial_state.value());
+    queue.push(initial_state);
 
-    while let Some(StateOrd(state)) = queue.pop() {
+    while let Some(state) = queue.pop() {
+        let key = state.duplication_key();
         if state.finished() {
-            return Some(state);
+            let mut path: Vec<S::DuplicationKey> =
+                std::iter::successors(Some(key), |key| predecessors.get(key).cloned()).collect();
+            path.reverse();
+            return Some(SearchResult { state, path });
         } else if visited
-            .get(&state.duplication_key())
+            .get(&key)
             .map(|v| state.value() <= *v)
             .unwrap_or(true)
         {
             for next_state in state.generate_moves() {
                 let dk = next_state.duplication_key();
                 let nv = next_state.value();
-                match visited.entry(dk) {
+                match visited.entry(dk.clone()) {
                     Entry::Occupied(mut occ) if nv < *occ.get() => {
                         occ.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        predecessors.insert(dk, key.clone());
+                        queue.push(next_state);
                     }
                     Entry::Vacant(vac) => {
                         vac.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        predecessors.insert(dk, key.clone());
+                        queue.push(next_state);
                     }
                     _ => {}
                 }
@@ -100,17 +280,26 @@ where
     None
 }
 
-pub fn astar_all_best<S>(initial_state: S) -> Vec<S>
+/**
+ * Search from `initial_state` for all goal states with the lowest value, one
+ * per duplication key, and the graph of all paths that reach them with the
+ * lowest value. Reaching a duplication key again with an equal value only
+ * records the predecessor, so the key is not expanded again.
+ */
+pub fn astar_all_best<S>(initial_state: S) -> BestPaths<S>
 where
     S: State,
 {
-    let mut queue: BinaryHeap<StateOrd<S>> = BinaryHeap::new();
+    let mut queue = Queue::new();
     let mut visited: HashMap<S::DuplicationKey, S::Value> = HashMap::new();
+    let mut predecessors: HashMap<S::DuplicationKey, Vec<S::DuplicationKey>> = HashMap::new();
 
-    queue.push(StateOrd(initial_state));
+    visited.insert(initial_state.duplication_key(), initial_state.value());
+    predecessors.insert(initial_state.duplication_key(), Vec::new());
+    queue.push(initial_state);
     let mut best: Vec<S> = Vec::new();
 
-    while let Some(StateOrd(state)) = queue.pop() {
+    while let Some(state) = queue.pop() {
         if !best.is_empty() && state.estimate() > best[0].value() {
             break;
         } else if state.finished() {
@@ -120,17 +309,26 @@ where
             .map(|v| state.value() <= *v)
             .unwrap_or(true)
         {
+            let key = state.duplication_key();
             for next_state in state.generate_moves() {
                 let dk = next_state.duplication_key();
                 let nv = next_state.value();
-                match visited.entry(dk) {
-                    Entry::Occupied(mut occ) if nv <= *occ.get() => {
+                match visited.entry(dk.clone()) {
+                    Entry::Occupied(mut occ) if nv < *occ.get() => {
                         occ.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        predecessors.insert(dk, vec![key.clone()]);
+                        queue.push(next_state);
+                    }
+                    Entry::Occupied(occ) if nv == *occ.get() => {
+                        let preds = predecessors.entry(dk).or_default();
+                        if !preds.contains(&key) {
+                            preds.push(key.clone());
+                        }
                     }
                     Entry::Vacant(vac) => {
                         vac.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        predecessors.insert(dk, vec![key.clone()]);
+                        queue.push(next_state);
                     }
                     _ => {}
                 }
@@ -138,21 +336,28 @@ where
         }
     }
 
-    best
+    BestPaths {
+        goals: best,
+        predecessors,
+    }
 }
 
+/**
+ * The lowest value of any state reachable from `initial_state`, for searches
+ * that minimize a value rather than look for a goal.
+ */
 pub fn astar_optimize<S>(initial_state: S) -> S::Value
 where
     S: State,
     S::Value: Clone,
 {
-    let mut queue: BinaryHeap<StateOrd<S>> = BinaryHeap::new();
+    let mut queue = Queue::new();
     let mut visited: HashMap<S::DuplicationKey, S::Value> = HashMap::new();
     let mut best = initial_state.value();
 
-    queue.push(StateOrd(initial_state));
+    queue.push(initial_state);
 
-    while let Some(StateOrd(state)) = queue.pop() {
+    while let Some(state) = queue.pop() {
         if state.estimate() > best {
             return best;
         } else if visited
@@ -167,11 +372,11 @@ where
                 match visited.entry(dk) {
                     Entry::Occupied(mut occ) if nv < *occ.get() => {
                         occ.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        queue.push(next_state);
                     }
                     Entry::Vacant(vac) => {
                         vac.insert(nv);
-                        queue.push(StateOrd(next_state));
+                        queue.push(next_state);
                     }
                     _ => {}
                 }
@@ -181,3 +386,167 @@ where
 
     best
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::astar;
+    use super::astar_all_best;
+    use super::State;
+
+    /**
+     * A walk on a diamond, where nodes 1 and 2 are both on a shortest path
+     * from node 0 to node 3, and a direct edge from 0 to 3 is longer.
+     */
+    #[derive(Debug, Eq, PartialEq)]
+    struct Diamond {
+        node: u8,
+        cost: u32,
+    }
+
+    impl State for Diamond {
+        type DuplicationKey = u8;
+        type Value = u32;
+        type NewStates = std::vec::IntoIter<Self>;
+
+        fn value(&self) -> u32 {
+            self.cost
+        }
+
+        fn estimate(&self) -> u32 {
+            self.cost + u32::from(self.node != 3)
+        }
+
+        fn duplication_key(&self) -> u8 {
+            self.node
+        }
+
+        fn generate_moves(self) -> Self::NewStates {
+            let edges: &[(u8, u32)] = match self.node {
+                0 => &[(1, 1), (2, 1), (3, 5)],
+                1 | 2 => &[(3, 1)],
+                _ => &[],
+            };
+            edges
+                .iter()
+                .map(|(node, cost)| Diamond {
+                    node: *node,
+                    cost: self.cost + cost,
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+    }
+
+    #[test]
+    fn astar_returns_path() {
+        let result = astar(Diamond { node: 0, cost: 0 }).unwrap();
+        assert_eq!(result.state, Diamond { node: 3, cost: 2 });
+        assert_eq!(result.path, [0, 1, 3]);
+    }
+
+    #[test]
+    fn all_best_returns_every_optimal_predecessor() {
+        let best = astar_all_best(Diamond { node: 0, cost: 0 });
+        assert!(best.goals.iter().all(|goal| goal.cost == 2));
+        let mut preds = best.predecessors[&3].clone();
+        preds.sort();
+        assert_eq!(preds, [1, 2]);
+        let mut keys: Vec<u8> = best.keys().into_iter().copied().collect();
+        keys.sort();
+        assert_eq!(keys, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn all_best_expands_equal_arrivals_once() {
+        let best = astar_all_best(Diamond { node: 0, cost: 0 });
+        assert_eq!(best.goals, [Diamond { node: 3, cost: 2 }]);
+    }
+
+    /**
+     * A fan of nodes 1 to 4 with equal estimates around node 0, where only
+     * node 4 leads on to the goal. Expanded nodes are recorded in `log`.
+     */
+    #[derive(Eq, PartialEq)]
+    struct Fan<'log> {
+        node: u8,
+        cost: u32,
+        prefer_high: bool,
+        log: &'log RefCell<Vec<u8>>,
+    }
+
+    impl<'log> State for Fan<'log> {
+        type DuplicationKey = u8;
+        type Value = u32;
+        type NewStates = std::vec::IntoIter<Self>;
+
+        fn value(&self) -> u32 {
+            self.cost
+        }
+
+        fn estimate(&self) -> u32 {
+            self.cost
+        }
+
+        fn duplication_key(&self) -> u8 {
+            self.node
+        }
+
+        fn generate_moves(self) -> Self::NewStates {
+            self.log.borrow_mut().push(self.node);
+            let nodes: &[u8] = match self.node {
+                0 => &[1, 2, 3, 4],
+                4 => &[5],
+                _ => &[],
+            };
+            nodes
+                .iter()
+                .map(|node| Fan {
+                    node: *node,
+                    cost: self.cost + 1,
+                    ..self
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn finished(&self) -> bool {
+            self.node == 5
+        }
+
+        fn tie_break(&self) -> i64 {
+            if self.prefer_high {
+                self.node.into()
+            } else {
+                0
+            }
+        }
+    }
+
+    fn expansions(prefer_high: bool) -> Vec<u8> {
+        let log = RefCell::new(Vec::new());
+        let result = astar(Fan {
+            node: 0,
+            cost: 0,
+            prefer_high,
+            log: &log,
+        });
+        assert_eq!(result.map(|result| result.path), Some(vec![0, 4, 5]));
+        log.into_inner()
+    }
+
+    #[test]
+    fn ties_are_expanded_in_order_generated() {
+        for _ in 0..10 {
+            assert_eq!(expansions(false), [0, 1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn ties_are_broken_by_tie_break() {
+        for _ in 0..10 {
+            assert_eq!(expansions(true), [0, 4, 3, 2, 1]);
+        }
+    }
+}
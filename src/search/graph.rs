@@ -0,0 +1,209 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+/*!
+ * Searches over graphs described by closures rather than by a [State] type.
+ * Each function takes the start node and a `successors` function giving the
+ * nodes one step away, and returns the cost of the cheapest path to a goal
+ * along with the path itself, from the start node to the goal. Like the
+ * [State] searches, they give up through [cancel::check] once the current
+ * thread is cancelled.
+ *
+ * [State]: super::astar::State
+ */
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::cancel;
+
+/**
+ * A* search from `start` for a node where `is_goal` holds. `successors` gives
+ * the neighbors of a node with the cost of the step to each, and `heuristic`
+ * a lower bound of the remaining cost from a node to a goal. The cost of the
+ * empty path is `C::default()`.
+ */
+pub fn astar_by<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    // Each node reached is stored once, with the index of its predecessor on
+    // the cheapest path found so far and the cost of that path.
+    let mut nodes: Vec<(N, Option<usize>, C)> = vec![(start.clone(), None, C::default())];
+    let mut indices: HashMap<N, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        cancel::check();
+        let (node, _, best_cost) = &nodes[i];
+        if cost > *best_cost {
+            continue;
+        } else if is_goal(node) {
+            return Some((cost, reconstruct_path(&nodes, i)));
+        }
+        let node = node.clone();
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            let j = match indices.entry(next) {
+                Entry::Occupied(occ) => {
+                    let j = *occ.get();
+                    if next_cost < nodes[j].2 {
+                        nodes[j].1 = Some(i);
+                        nodes[j].2 = next_cost;
+                        j
+                    } else {
+                        continue;
+                    }
+                }
+                Entry::Vacant(vac) => {
+                    nodes.push((vac.key().clone(), Some(i), next_cost));
+                    *vac.insert(nodes.len() - 1)
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[j].0);
+            queue.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/**
+ * Dijkstra's algorithm: [astar_by] without a heuristic.
+ */
+pub fn dijkstra_by<N, C, FN, IN, FG>(start: N, successors: FN, is_goal: FG) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar_by(start, successors, |_| C::default(), is_goal)
+}
+
+/**
+ * Breadth-first search from `start` for a node where `is_goal` holds, where
+ * every step costs 1. `successors` gives the neighbors of a node.
+ */
+pub fn bfs_by<N, FN, IN, FG>(
+    start: N,
+    mut successors: FN,
+    mut is_goal: FG,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut nodes: Vec<(N, Option<usize>, usize)> = vec![(start.clone(), None, 0)];
+    let mut indices: HashMap<N, usize> = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        cancel::check();
+        let (node, _, steps) = &nodes[i];
+        if is_goal(node) {
+            return Some((*steps, reconstruct_path(&nodes, i)));
+        }
+        let (node, steps) = (node.clone(), *steps);
+        for next in successors(&node) {
+            if let Entry::Vacant(vac) = indices.entry(next) {
+                nodes.push((vac.key().clone(), Some(i), steps + 1));
+                vac.insert(nodes.len() - 1);
+                queue.push_back(nodes.len() - 1);
+            }
+        }
+    }
+
+    None
+}
+
+/**
+ * The nodes from the start to `nodes[end]`, following the predecessor indices.
+ */
+fn reconstruct_path<N, C>(nodes: &[(N, Option<usize>, C)], end: usize) -> Vec<N>
+where
+    N: Clone,
+{
+    let mut path: Vec<N> = std::iter::successors(Some(end), |i| nodes[*i].1)
+        .map(|i| nodes[i].0.clone())
+        .collect();
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::astar_by;
+    use super::bfs_by;
+    use super::dijkstra_by;
+
+    /** A line of nodes 0..=10, with a costly shortcut from 0 to 10. */
+    fn line(n: &u32) -> Vec<(u32, u32)> {
+        let mut next = vec![(n + 1, 1)];
+        if *n == 0 {
+            next.push((10, 15));
+        }
+        next.retain(|(m, _)| *m <= 10);
+        next
+    }
+
+    #[test]
+    fn dijkstra_takes_cheapest_path() {
+        assert_eq!(
+            dijkstra_by(0, line, |n| *n == 10),
+            Some((10, (0..=10).collect()))
+        );
+    }
+
+    #[test]
+    fn astar_with_heuristic() {
+        assert_eq!(
+            astar_by(0, line, |n| 10 - n, |n| *n == 10),
+            Some((10, (0..=10).collect()))
+        );
+        assert_eq!(astar_by(0, line, |_| 0, |n| *n == 11), None);
+    }
+
+    #[test]
+    fn bfs_takes_fewest_steps() {
+        let (steps, path) =
+            bfs_by(0, |n| line(n).into_iter().map(|(m, _)| m), |n| *n == 10).unwrap();
+        assert_eq!((steps, path), (1, vec![0, 10]));
+        assert_eq!(
+            bfs_by(3, |n| [n + 1].into_iter().filter(|m| *m < 5), |n| *n == 7),
+            None
+        );
+        assert_eq!(bfs_by(3, |_| [], |n| *n == 3), Some((0, vec![3])));
+    }
+}
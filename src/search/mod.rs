@@ -14,4 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+/*!
+ * Generic search algorithms over implicit graphs, where each state generates
+ * its successors. The graph is described either by implementing
+ * [astar::State], or by closures passed to the functions in [graph].
+ */
+
 pub mod astar;
+pub mod graph;
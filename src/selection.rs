@@ -0,0 +1,162 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/**
+ * Parse a day selection expression: a comma-separated list of terms, each of
+ * which is a day number (`7`), an inclusive range (`1-10`), `all` or `latest`
+ * (the highest available day). A term prefixed with `!` excludes those days
+ * instead; if every term is an exclusion, they are excluded from all days.
+ *
+ * Every day must be one of the `available` days. The selected days are
+ * returned in ascending order.
+ */
+pub fn parse_days(expr: &str, available: &[u8]) -> Result<Vec<u8>, String> {
+    let mut included = BTreeSet::new();
+    let mut excluded = BTreeSet::new();
+    let mut any_included = false;
+
+    for term in expr.split(',').map(str::trim) {
+        if term.is_empty() {
+            return Err(format!("Empty term in day selection: {expr:?}"));
+        }
+        match term.strip_prefix('!') {
+            Some(term) => excluded.extend(parse_term(term.trim(), available)?),
+            None => {
+                included.extend(parse_term(term, available)?);
+                any_included = true;
+            }
+        }
+    }
+
+    if !any_included {
+        included.extend(available);
+    }
+    Ok(included.difference(&excluded).copied().collect())
+}
+
+fn parse_term(term: &str, available: &[u8]) -> Result<Vec<u8>, String> {
+    match term {
+        "all" => Ok(available.to_vec()),
+        "latest" => available
+            .iter()
+            .max()
+            .map(|day| vec![*day])
+            .ok_or_else(|| "No days available".to_string()),
+        _ => match term.split_once('-') {
+            Some((first, last)) => {
+                let first = parse_day(first.trim(), available)?;
+                let last = parse_day(last.trim(), available)?;
+                if first > last {
+                    return Err(format!("Empty day range: {term}"));
+                }
+                Ok(available
+                    .iter()
+                    .copied()
+                    .filter(|day| (first..=last).contains(day))
+                    .collect())
+            }
+            None => Ok(vec![parse_day(term, available)?]),
+        },
+    }
+}
+
+fn parse_day(s: &str, available: &[u8]) -> Result<u8, String> {
+    let day: u8 = s
+        .parse()
+        .map_err(|_| format!("Invalid day number: {s:?}"))?;
+    if available.contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("Unknown day: {day}"))
+    }
+}
+
+/**
+ * Parse an input file override of the form `DAY=PATH`.
+ */
+pub fn parse_input_override(s: &str, available: &[u8]) -> Result<(u8, PathBuf), String> {
+    let (day, path) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Expected DAY=PATH, found {s:?}"))?;
+    if path.is_empty() {
+        return Err(format!("Missing path for day {day}"));
+    }
+    Ok((parse_day(day.trim(), available)?, PathBuf::from(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_days;
+    use super::parse_input_override;
+    use std::path::PathBuf;
+
+    const AVAILABLE: [u8; 5] = [1, 2, 3, 5, 8];
+
+    #[test]
+    fn selects_single_days_lists_and_ranges() {
+        assert_eq!(parse_days("3", &AVAILABLE), Ok(vec![3]));
+        assert_eq!(parse_days("8,1, 3", &AVAILABLE), Ok(vec![1, 3, 8]));
+        assert_eq!(parse_days("2-5", &AVAILABLE), Ok(vec![2, 3, 5]));
+        assert_eq!(parse_days("1-2,5-8", &AVAILABLE), Ok(vec![1, 2, 5, 8]));
+    }
+
+    #[test]
+    fn selects_all_and_latest() {
+        assert_eq!(parse_days("all", &AVAILABLE), Ok(AVAILABLE.to_vec()));
+        assert_eq!(parse_days("latest", &AVAILABLE), Ok(vec![8]));
+    }
+
+    #[test]
+    fn exclusions_apply_to_all_days_by_default() {
+        assert_eq!(parse_days("!3", &AVAILABLE), Ok(vec![1, 2, 5, 8]));
+        assert_eq!(parse_days("!latest,!1-2", &AVAILABLE), Ok(vec![3, 5]));
+        assert_eq!(parse_days("1-5,!2", &AVAILABLE), Ok(vec![1, 3, 5]));
+    }
+
+    #[test]
+    fn rejects_unknown_and_malformed_days() {
+        assert_eq!(
+            parse_days("4", &AVAILABLE),
+            Err("Unknown day: 4".to_string())
+        );
+        assert_eq!(
+            parse_days("1-9", &AVAILABLE),
+            Err("Unknown day: 9".to_string())
+        );
+        assert_eq!(
+            parse_days("5-1", &AVAILABLE),
+            Err("Empty day range: 5-1".to_string())
+        );
+        assert_eq!(
+            parse_days("x", &AVAILABLE),
+            Err("Invalid day number: \"x\"".to_string())
+        );
+        assert!(parse_days("1,,2", &AVAILABLE).is_err());
+    }
+
+    #[test]
+    fn parses_input_overrides() {
+        assert_eq!(
+            parse_input_override("3=foo=bar.txt", &AVAILABLE),
+            Ok((3, PathBuf::from("foo=bar.txt")))
+        );
+        assert!(parse_input_override("3", &AVAILABLE).is_err());
+        assert!(parse_input_override("4=foo.txt", &AVAILABLE).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::common::ParseError;
+use crate::common::Solution;
+use crate::input::Input;
+
+/**
+ * One of the two parts of a day's puzzle.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::A, Part::B];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" => Ok(Self::A),
+            "b" | "B" => Ok(Self::B),
+            _ => Err(format!("Unknown part: {s}")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::A => write!(f, "A"),
+            Self::B => write!(f, "B"),
+        }
+    }
+}
+
+/**
+ * A published example input with its expected answers. A part without an
+ * expected answer does not apply to the example, and is not run.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    /** The input, after a leading newline which is not part of it. */
+    pub input: &'static str,
+    pub part_a: Option<&'static str>,
+    pub part_b: Option<&'static str>,
+    /** Parameter overrides the example is meant to be solved with. */
+    pub params: &'static [(&'static str, i64)],
+}
+
+impl Example {
+    pub fn input(&self) -> Input {
+        Input::new(self.input.strip_prefix('\n').unwrap_or(self.input))
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::A => self.part_a,
+            Part::B => self.part_b,
+        }
+    }
+}
+
+/**
+ * A tunable puzzle parameter, such as a grid size or iteration count, that
+ * the published examples use a different value for than the real input.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub description: &'static str,
+}
+
+/**
+ * Values for a day's [Param]s, starting from their defaults.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Params {
+    values: BTreeMap<&'static str, i64>,
+}
+
+impl Params {
+    pub fn defaults(specs: &'static [Param]) -> Self {
+        Self {
+            values: specs.iter().map(|spec| (spec.name, spec.default)).collect(),
+        }
+    }
+
+    /**
+     * Override the parameter `name` with `value`, which must be an integer no
+     * less than the parameter's minimum.
+     */
+    pub fn with(
+        mut self,
+        specs: &'static [Param],
+        name: &str,
+        value: &str,
+    ) -> Result<Self, String> {
+        let spec = specs
+            .iter()
+            .find(|spec| spec.name == name)
+            .ok_or_else(|| format!("Unknown parameter: {name}"))?;
+        let value: i64 = value
+            .parse()
+            .map_err(|_| format!("Invalid value for parameter {name}: {value}"))?;
+        if value < spec.min {
+            return Err(format!(
+                "Parameter {name} must be at least {}, was {value}",
+                spec.min
+            ));
+        }
+        self.values.insert(spec.name, value);
+        Ok(self)
+    }
+
+    /**
+     * The value of the parameter `name`, which the day must declare.
+     */
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: TryFrom<i64>,
+    {
+        let value = *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("Undeclared parameter: {name}"));
+        T::try_from(value).unwrap_or_else(|_| panic!("Parameter {name} out of range: {value}"))
+    }
+}
+
+impl Display for Params {
+    /**
+     * Formats the values as `name=value` pairs separated by commas.
+     */
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/**
+ * A day's puzzle, split into parsing the input and solving each part from the
+ * parsed input.
+ */
+pub trait Solver: Sync {
+    const DAY: u8;
+    /** The title of the puzzle, without the "Day N:" prefix. */
+    const TITLE: &'static str;
+    const EXAMPLES: &'static [Example];
+    const PARAMS: &'static [Param] = &[];
+    type Parsed<'input>;
+
+    fn parse<'input>(&self, input: &'input Input) -> Result<Self::Parsed<'input>, ParseError>;
+    fn part_a(&self, parsed: &Self::Parsed<'_>, params: &Params) -> String;
+    fn part_b(&self, parsed: &Self::Parsed<'_>, params: &Params) -> String;
+}
+
+/**
+ * Object-safe view of a [Solver], as registered by the `days!` macro.
+ */
+pub trait DaySolver: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn examples(&self) -> &'static [Example];
+    fn params(&self) -> &'static [Param];
+    fn parse<'s>(&'s self, input: &'s Input) -> Result<Box<dyn ParsedInput + 's>, ParseError>;
+
+    /**
+     * Solve both parts with the default parameters.
+     */
+    fn solve(&self, input: &Input) -> Result<Solution, ParseError> {
+        let params = Params::defaults(self.params());
+        let parsed = self.parse(input)?;
+        Ok((parsed.part_a(&params), parsed.part_b(&params)))
+    }
+
+    /**
+     * The default parameters with an example's overrides applied.
+     */
+    fn example_params(&self, example: &Example) -> Params {
+        example
+            .params
+            .iter()
+            .fold(Params::defaults(self.params()), |params, (name, value)| {
+                params
+                    .with(self.params(), name, &value.to_string())
+                    .unwrap_or_else(|e| panic!("Day {}: {e}", self.day()))
+            })
+    }
+}
+
+/**
+ * An input parsed by a [DaySolver], ready to solve either part.
+ */
+pub trait ParsedInput {
+    fn part_a(&self, params: &Params) -> String;
+    fn part_b(&self, params: &Params) -> String;
+
+    fn part(&self, part: Part, params: &Params) -> String {
+        match part {
+            Part::A => self.part_a(params),
+            Part::B => self.part_b(params),
+        }
+    }
+}
+
+struct WithSolver<'s, S>
+where
+    S: Solver,
+{
+    solver: &'s S,
+    parsed: S::Parsed<'s>,
+}
+
+impl<S> ParsedInput for WithSolver<'_, S>
+where
+    S: Solver,
+{
+    fn part_a(&self, params: &Params) -> String {
+        self.solver.part_a(&self.parsed, params)
+    }
+
+    fn part_b(&self, params: &Params) -> String {
+        self.solver.part_b(&self.parsed, params)
+    }
+}
+
+impl<S> DaySolver for S
+where
+    S: Solver,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn parse<'s>(&'s self, input: &'s Input) -> Result<Box<dyn ParsedInput + 's>, ParseError> {
+        let parsed = Solver::parse(self, input).map_err(|e| e.in_day(S::DAY))?;
+        Ok(Box::new(WithSolver {
+            solver: self,
+            parsed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "size",
+            default: 70,
+            min: 1,
+            description: "Grid size",
+        },
+        Param {
+            name: "steps",
+            default: 100,
+            min: 0,
+            description: "Number of steps",
+        },
+    ];
+
+    #[test]
+    fn params_default() {
+        let params = Params::defaults(PARAMS);
+        assert_eq!(params.get::<usize>("size"), 70);
+        assert_eq!(params.get::<i64>("steps"), 100);
+    }
+
+    #[test]
+    fn params_override() {
+        let params = Params::defaults(PARAMS).with(PARAMS, "size", "6").unwrap();
+        assert_eq!(params.get::<usize>("size"), 6);
+        assert_eq!(params.get::<usize>("steps"), 100);
+        assert_eq!(params.to_string(), "size=6,steps=100");
+    }
+
+    #[test]
+    fn params_reject_invalid() {
+        let params = Params::defaults(PARAMS);
+        assert!(params.clone().with(PARAMS, "width", "6").is_err());
+        assert!(params.clone().with(PARAMS, "size", "six").is_err());
+        assert!(params.clone().with(PARAMS, "size", "0").is_err());
+        assert!(params.with(PARAMS, "steps", "0").is_ok());
+    }
+}
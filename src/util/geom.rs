@@ -0,0 +1,379 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+/*!
+ * Positions, displacements and directions on a grid of rows and columns, with
+ * rows counted downwards.
+ */
+
+use std::fmt::Display;
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Neg;
+
+/**
+ * A position on a grid, as a row and a column.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Pos {
+    pub r: usize,
+    pub c: usize,
+}
+
+/**
+ * A displacement between two positions, in rows and columns.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Delta {
+    pub dr: isize,
+    pub dc: isize,
+}
+
+/**
+ * One of the 4 orthogonal directions.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir4 {
+    Up = 0,
+    Right = 1,
+    Down = 2,
+    Left = 3,
+}
+
+/**
+ * One of the 8 orthogonal and diagonal directions.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir8 {
+    Up = 0,
+    UpRight = 1,
+    Right = 2,
+    DownRight = 3,
+    Down = 4,
+    DownLeft = 5,
+    Left = 6,
+    UpLeft = 7,
+}
+
+impl Pos {
+    pub const fn new(r: usize, c: usize) -> Self {
+        Self { r, c }
+    }
+
+    /**
+     * The position `delta` away, or [None] if that has a negative row or
+     * column.
+     */
+    pub fn checked_add(self, delta: Delta) -> Option<Self> {
+        Some(Self::new(
+            self.r.checked_add_signed(delta.dr)?,
+            self.c.checked_add_signed(delta.dc)?,
+        ))
+    }
+
+    /**
+     * The position `delta` away, wrapping around at the bounds of [usize].
+     */
+    pub fn wrapping_add(self, delta: Delta) -> Self {
+        Self::new(
+            self.r.wrapping_add_signed(delta.dr),
+            self.c.wrapping_add_signed(delta.dc),
+        )
+    }
+
+    /**
+     * The displacement from `self` to `other`.
+     */
+    pub fn delta_to(self, other: Self) -> Delta {
+        Delta::new(
+            other.r as isize - self.r as isize,
+            other.c as isize - self.c as isize,
+        )
+    }
+
+    /**
+     * The number of orthogonal steps between `self` and `other`.
+     */
+    pub fn manhattan(self, other: Self) -> usize {
+        self.r.abs_diff(other.r) + self.c.abs_diff(other.c)
+    }
+
+    /**
+     * The number of orthogonal or diagonal steps between `self` and `other`.
+     */
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.r.abs_diff(other.r).max(self.c.abs_diff(other.c))
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((r, c): (usize, usize)) -> Self {
+        Self::new(r, c)
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(Pos { r, c }: Pos) -> Self {
+        (r, c)
+    }
+}
+
+impl Delta {
+    pub const fn new(dr: isize, dc: isize) -> Self {
+        Self { dr, dc }
+    }
+
+    pub fn manhattan(self) -> usize {
+        self.dr.unsigned_abs() + self.dc.unsigned_abs()
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.dr.unsigned_abs().max(self.dc.unsigned_abs())
+    }
+}
+
+impl From<(isize, isize)> for Delta {
+    fn from((dr, dc): (isize, isize)) -> Self {
+        Self::new(dr, dc)
+    }
+}
+
+impl Add for Delta {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.dr + rhs.dr, self.dc + rhs.dc)
+    }
+}
+
+impl Mul<isize> for Delta {
+    type Output = Self;
+    fn mul(self, rhs: isize) -> Self {
+        Self::new(self.dr * rhs, self.dc * rhs)
+    }
+}
+
+impl Neg for Delta {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.dr, -self.dc)
+    }
+}
+
+impl Dir4 {
+    /**
+     * All directions, clockwise from up.
+     */
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /**
+     * The position of this direction in [Self::ALL].
+     */
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    /**
+     * One step in this direction.
+     */
+    pub const fn delta(self) -> Delta {
+        match self {
+            Self::Up => Delta::new(-1, 0),
+            Self::Right => Delta::new(0, 1),
+            Self::Down => Delta::new(1, 0),
+            Self::Left => Delta::new(0, -1),
+        }
+    }
+
+    /**
+     * The direction an arrow `^`, `>`, `v` or `<` points in.
+     */
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /**
+     * The arrow `^`, `>`, `v` or `<` that points in this direction.
+     */
+    pub const fn to_char(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+}
+
+impl From<Dir4> for Delta {
+    fn from(dir: Dir4) -> Self {
+        dir.delta()
+    }
+}
+
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl Dir8 {
+    /**
+     * All directions, clockwise from up.
+     */
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /**
+     * The position of this direction in [Self::ALL].
+     */
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /**
+     * The direction an eighth of a turn clockwise.
+     */
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /**
+     * The direction an eighth of a turn counterclockwise.
+     */
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /**
+     * One step in this direction.
+     */
+    pub const fn delta(self) -> Delta {
+        match self {
+            Self::Up => Delta::new(-1, 0),
+            Self::UpRight => Delta::new(-1, 1),
+            Self::Right => Delta::new(0, 1),
+            Self::DownRight => Delta::new(1, 1),
+            Self::Down => Delta::new(1, 0),
+            Self::DownLeft => Delta::new(1, -1),
+            Self::Left => Delta::new(0, -1),
+            Self::UpLeft => Delta::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir.index() * 2]
+    }
+}
+
+impl From<Dir8> for Delta {
+    fn from(dir: Dir8) -> Self {
+        dir.delta()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Delta;
+    use super::Dir4;
+    use super::Dir8;
+    use super::Pos;
+
+    #[test]
+    fn dir4_turns() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.turn_left().turn_left(), dir.reverse());
+            assert_eq!(dir.reverse().delta(), -dir.delta());
+            assert_eq!(Dir4::from_char(dir.to_char()), Some(dir));
+        }
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::from_char('x'), None);
+    }
+
+    #[test]
+    fn dir8_turns() {
+        for dir in Dir8::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.reverse().delta(), -dir.delta());
+            assert_eq!(dir.delta().chebyshev(), 1);
+        }
+        for dir in Dir4::ALL {
+            assert_eq!(Dir8::from(dir).delta(), dir.delta());
+            assert_eq!(
+                Dir8::from(dir).turn_right().turn_right(),
+                dir.turn_right().into()
+            );
+        }
+    }
+
+    #[test]
+    fn displacement() {
+        let pos = Pos::new(0, 2);
+        assert_eq!(pos.checked_add(Dir4::Right.delta()), Some(Pos::new(0, 3)));
+        assert_eq!(pos.checked_add(Dir4::Up.delta()), None);
+        assert_eq!(pos.wrapping_add(Dir4::Up.delta()), Pos::new(usize::MAX, 2));
+        assert_eq!(
+            pos.checked_add(Dir4::Down.delta() * 3 + Dir4::Left.delta()),
+            Some(Pos::new(3, 1))
+        );
+        assert_eq!(pos.delta_to(Pos::new(3, 1)), Delta::new(3, -1));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Pos::new(1, 5);
+        let b = Pos::new(4, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.delta_to(b).manhattan(), 7);
+        assert_eq!(b.delta_to(a).chebyshev(), 4);
+    }
+}
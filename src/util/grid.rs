@@ -0,0 +1,422 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+/*!
+ * A rectangular grid of cells, stored row by row in one buffer.
+ */
+
+use std::fmt::Display;
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use crate::common::check_width;
+use crate::common::ParseError;
+use crate::util::geom::Delta;
+use crate::util::geom::Dir4;
+use crate::util::geom::Dir8;
+use crate::util::geom::Pos;
+
+/**
+ * A grid of `width` by `height` cells. Cells are addressed by `(row, column)`
+ * tuples or by [Pos].
+ */
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /**
+     * A grid of `width` by `height` cells, all set to `value`.
+     */
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /**
+     * A grid of `width` by `height` cells, each set to `f` of its position.
+     */
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|r| (0..width).map(move |c| (r, c)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /**
+     * Parse numbered lines into a grid, one row per non-empty line and one
+     * cell per character, mapped through `f`. The lines must all have the same
+     * number of characters, and a character that `f` maps to [None] is
+     * reported as an error that expected `expected`.
+     */
+    pub fn parse<'a, I, F>(lines: I, expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = (usize, &'a str)>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (line_num, line) in lines.into_iter().filter(|(_, line)| !line.is_empty()) {
+            let width = *width.get_or_insert_with(|| line.chars().count());
+            check_width(line_num, line, width)?;
+            for (column, ch) in (1..).zip(line.chars()) {
+                cells.push(f(ch).ok_or_else(|| {
+                    ParseError::new(line_num, column, expected, format!("{ch:?}"))
+                })?);
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains<P>(&self, pos: P) -> bool
+    where
+        P: Into<(usize, usize)>,
+    {
+        let (r, c) = pos.into();
+        r < self.height && c < self.width
+    }
+
+    pub fn get<P>(&self, pos: P) -> Option<&T>
+    where
+        P: Into<(usize, usize)>,
+    {
+        self.index_of(pos.into()).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<P>(&mut self, pos: P) -> Option<&mut T>
+    where
+        P: Into<(usize, usize)>,
+    {
+        self.index_of(pos.into()).map(|i| &mut self.cells[i])
+    }
+
+    /**
+     * The position `delta` away from `pos`, or [None] if that is outside the
+     * grid.
+     */
+    pub fn offset<D>(&self, pos: (usize, usize), delta: D) -> Option<(usize, usize)>
+    where
+        D: Into<Delta>,
+    {
+        Pos::from(pos)
+            .checked_add(delta.into())
+            .map(<(usize, usize)>::from)
+            .filter(|pos| self.contains(*pos))
+    }
+
+    /**
+     * Every cell with its position, row by row.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /**
+     * Every position in the grid, row by row.
+     */
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /**
+     * The position of the first cell equal to `value`, row by row.
+     */
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /**
+     * The positions of all cells equal to `value`, row by row.
+     */
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /**
+     * The orthogonal neighbors of `pos` that are inside the grid, in the order
+     * of [Dir4::ALL].
+     */
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir))
+    }
+
+    /**
+     * The orthogonal and diagonal neighbors of `pos` that are inside the grid,
+     * in the order of [Dir8::ALL].
+     */
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, dir))
+    }
+
+    /**
+     * Row `r`, from left to right.
+     */
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    /**
+     * Every row, from top to bottom.
+     */
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    /**
+     * Column `c`, from top to bottom.
+     */
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.width, "column {c} out of bounds");
+        self.cells.iter().skip(c).step_by(self.width)
+    }
+
+    /**
+     * A grid of the same shape with `f` applied to every cell.
+     */
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /**
+     * The grid mirrored along its main diagonal, so rows become columns.
+     */
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(r, c)| self[(c, r)].clone())
+    }
+
+    /**
+     * The grid turned a quarter turn clockwise.
+     */
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(r, c)| {
+            self[(self.height - 1 - c, r)].clone()
+        })
+    }
+
+    /**
+     * The grid turned a quarter turn counterclockwise.
+     */
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(r, c)| {
+            self[(c, self.width - 1 - r)].clone()
+        })
+    }
+
+    fn index_of(&self, pos: (usize, usize)) -> Option<usize> {
+        let (r, c) = pos;
+        self.contains(pos).then(|| r * self.width + c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds"))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        &self[<(usize, usize)>::from(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        &mut self[<(usize, usize)>::from(pos)]
+    }
+}
+
+/**
+ * The cells row by row, with a newline after each row.
+ */
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::common::ParseError;
+
+    fn parse(text: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse((1..).zip(text.lines()), "'#' or '.'", |ch| {
+            Some(ch).filter(|ch| "#.".contains(*ch))
+        })
+    }
+
+    #[test]
+    fn parse_skips_empty_lines() {
+        let grid = parse("#..\n\n.#.\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &['.', '#', '.']);
+        assert_eq!(grid.to_string(), "#..\n.#.\n");
+    }
+
+    #[test]
+    fn parse_reports_bad_cells_and_ragged_rows() {
+        assert_eq!(
+            parse("#..\n.x.\n"),
+            Err(ParseError::new(2, 2, "'#' or '.'", "'x'"))
+        );
+        assert_eq!(
+            parse("#..\n.#\n"),
+            Err(ParseError::new(2, 3, "3 columns", "end of line"))
+        );
+        assert_eq!(
+            parse("#..\n.#..\n"),
+            Err(ParseError::new(2, 4, "3 columns", "\".\""))
+        );
+    }
+
+    #[test]
+    fn parse_reports_columns_in_characters() {
+        let parse = |text: &str| {
+            Grid::parse((1..).zip(text.lines()), "'·' or '#'", |ch| {
+                Some(ch).filter(|ch| "·#".contains(*ch))
+            })
+        };
+        assert_eq!(
+            parse("··#\n·x#\n"),
+            Err(ParseError::new(2, 2, "'·' or '#'", "'x'"))
+        );
+        assert_eq!(
+            parse("··#\n·#\n"),
+            Err(ParseError::new(2, 3, "3 columns", "end of line"))
+        );
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let grid = parse("#..\n.#.\n").unwrap();
+        assert_eq!(grid.get((1, 2)), Some(&'.'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((usize::MAX, usize::MAX)), None);
+    }
+
+    #[test]
+    fn find_and_neighbors() {
+        let grid = parse("#..\n.#.\n").unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8((1, 2)).collect::<Vec<_>>(),
+            [(0, 2), (1, 1), (0, 1)]
+        );
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+    }
+
+    #[test]
+    fn columns_and_rotations() {
+        let grid = Grid::from_fn(3, 2, |(r, c)| r * 3 + c);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [1, 4]);
+        assert_eq!(grid.transpose().to_string(), "03\n14\n25\n");
+        assert_eq!(grid.rotate_cw().to_string(), "30\n41\n52\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "25\n14\n03\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
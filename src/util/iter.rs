@@ -14,9 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+/*!
+ * Extension traits for iterators and slices.
+ */
+
 use std::collections::HashMap;
 use std::ops::MulAssign;
 
+/**
+ * Iterator over the overlapping pairs of consecutive items of another
+ * iterator. See [WithSliding::sliding2].
+ */
 pub struct Sliding2<I, T> {
     buffer: Option<T>,
     iter: I,
@@ -43,6 +51,10 @@ where
     Self: Iterator,
     Self: Sized,
 {
+    /**
+     * The overlapping pairs of consecutive items: `(a, b)`, `(b, c)`, and so
+     * on.
+     */
     fn sliding2(mut self) -> Sliding2<Self, Self::Item> {
         Sliding2 {
             buffer: self.next(),
@@ -53,6 +65,10 @@ where
 
 impl<I> WithSliding for I where I: Iterator {}
 
+/**
+ * Iterator over the unordered pairs of distinct items of a slice. See
+ * [WithPairs::pairs].
+ */
 pub struct Pairs<'a, T> {
     seq: &'a [T],
     i: usize,
@@ -80,7 +96,11 @@ pub trait WithPairs<T>
 where
     Self: AsRef<[T]>,
 {
-    fn pairs(&self) -> Pairs<T> {
+    /**
+     * Each pair of items at different positions, once, with the earlier item
+     * first.
+     */
+    fn pairs(&self) -> Pairs<'_, T> {
         Pairs {
             seq: self.as_ref(),
             i: 0,
@@ -99,6 +119,9 @@ where
     Int: PartialEq<Int>,
     Int: MulAssign<Int>,
 {
+    /**
+     * The product of the items, stopping at the first zero.
+     */
     fn lazy_product(self) -> Int {
         let mut product = Int::from(1_u8);
         let zero: Int = Int::from(0_u8);
@@ -123,7 +146,13 @@ where
 }
 
 pub trait Countable<A> {
+    /**
+     * Add the number of occurrences of each item to the counts in `init`.
+     */
     fn counts_into(self, init: HashMap<A, usize>) -> HashMap<A, usize>;
+    /**
+     * The number of occurrences of each item.
+     */
     fn counts(self) -> HashMap<A, usize>
     where
         Self: Sized,
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-#![allow(unused)]
+/*!
+ * Arithmetic helpers, iterator extensions in [iter], the [grid::Grid] type and
+ * the position and direction types in [geom].
+ */
 
 use std::ops::Add;
 use std::ops::Div;
@@ -22,8 +25,13 @@ use std::ops::Mul;
 use std::ops::Rem;
 use std::ops::Sub;
 
+pub mod geom;
+pub mod grid;
 pub mod iter;
 
+/**
+ * The greatest common divisor of `a` and `b`.
+ */
 pub fn gcd(a: usize, b: usize) -> usize {
     if b == 0 {
         a
@@ -32,6 +40,9 @@ pub fn gcd(a: usize, b: usize) -> usize {
     }
 }
 
+/**
+ * The least common multiple of `a` and `b`.
+ */
 pub fn lcm(a: usize, b: usize) -> usize {
     let gcdab = gcd(a, b);
     (a / gcdab) * b
@@ -0,0 +1,145 @@
+// Solutions to Advent of Code 2024
+// Copyright (C) 2024  Emil Lundberg <emil@emlun.se>
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::path::PathBuf;
+
+use crate::report::DayReport;
+use adventofcode_2024::solver::Part;
+
+/**
+ * Polls a set of files for changes to their content. A file that can't be read
+ * counts as a change when it is removed and again when it reappears.
+ */
+pub struct InputWatcher {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl InputWatcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let content = std::fs::read(&path).ok();
+                    (path, content)
+                })
+                .collect(),
+        }
+    }
+
+    /**
+     * The indices of the files whose content changed since the watcher was
+     * created or this was last called.
+     */
+    pub fn changed(&mut self) -> Vec<usize> {
+        self.files
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, (path, content))| {
+                let new_content = std::fs::read(path).ok();
+                if new_content != *content {
+                    *content = new_content;
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/**
+ * How the answers in `current` differ from those in `previous`, one line per
+ * part, like `A: 11 -> 12` or `B: 31 (unchanged)`. A missing answer is shown
+ * as `-`.
+ */
+pub fn answer_changes(previous: &DayReport, current: &DayReport) -> Vec<String> {
+    [
+        (Part::A, &previous.solution_a, &current.solution_a),
+        (Part::B, &previous.solution_b, &current.solution_b),
+    ]
+    .into_iter()
+    .filter_map(|(part, previous, current)| match (previous, current) {
+        (None, None) => None,
+        (Some(previous), Some(current)) if previous == current => {
+            Some(format!("{part}: {current} (unchanged)"))
+        }
+        (previous, current) => Some(format!(
+            "{part}: {} -> {}",
+            previous.as_deref().unwrap_or("-"),
+            current.as_deref().unwrap_or("-")
+        )),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::answer_changes;
+    use super::InputWatcher;
+    use crate::report::DayReport;
+
+    #[test]
+    fn watcher_reports_changed_content() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (one, two) = (dir.join("day01.in"), dir.join("day02.in"));
+        std::fs::write(&one, "1 2\n").unwrap();
+        std::fs::write(&two, "3 4\n").unwrap();
+
+        let mut watcher = InputWatcher::new(vec![one.clone(), two.clone()]);
+        assert_eq!(watcher.changed(), Vec::<usize>::new());
+        std::fs::write(&two, "3 5\n").unwrap();
+        assert_eq!(watcher.changed(), vec![1]);
+        assert_eq!(watcher.changed(), Vec::<usize>::new());
+        std::fs::write(&one, "1 2\n").unwrap();
+        assert_eq!(watcher.changed(), Vec::<usize>::new());
+        std::fs::remove_file(&one).unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+        std::fs::write(&one, "1 2\n").unwrap();
+        assert_eq!(watcher.changed(), vec![0]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn answer_changes_per_part() {
+        let previous = DayReport {
+            solution_a: Some("11".to_string()),
+            solution_b: Some("31".to_string()),
+            ..Default::default()
+        };
+        let current = DayReport {
+            solution_a: Some("12".to_string()),
+            solution_b: Some("31".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            answer_changes(&previous, &current),
+            vec!["A: 11 -> 12", "B: 31 (unchanged)"]
+        );
+
+        let panicked = DayReport {
+            solution_a: Some("12".to_string()),
+            error: Some("Part B panicked: oops".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            answer_changes(&current, &panicked),
+            vec!["A: 12 (unchanged)", "B: 31 -> -"]
+        );
+    }
+}
@@ -14,63 +14,54 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use adventofcode_2024::answers::Answers;
 use adventofcode_2024::common::day_input_filename;
-use adventofcode_2024::common::get_file_lines;
 use adventofcode_2024::days;
+use adventofcode_2024::input::Input;
+use adventofcode_2024::solver::Params;
+use adventofcode_2024::solver::Part;
 
-fn test_day(day: u8, correct_a: &str, correct_b: &str) -> Result<(), std::io::Error> {
-    let solve = days::get_solver(day).unwrap();
-    let input_lines = get_file_lines(&day_input_filename(day))?;
-    let (solution_a, solution_b) = solve(&input_lines);
-    assert_eq!(
-        solution_a.as_str(),
-        correct_a,
-        "Incorrect solution for day {}a",
-        day
-    );
-    assert_eq!(
-        solution_b.as_str(),
-        correct_b,
-        "Incorrect solution for day {}b",
-        day
-    );
+fn test_day(day: u8) -> Result<(), std::io::Error> {
+    let answers = Answers::parse(include_str!("../answers.toml")).unwrap_or_else(|e| panic!("{e}"));
+    let solver = days::get_solver(day).unwrap();
+    let input = Input::read(&day_input_filename(day))?;
+    let parsed = solver.parse(&input).unwrap_or_else(|e| panic!("{e}"));
+    let params = Params::defaults(solver.params());
+    for part in Part::ALL {
+        assert_eq!(
+            Some(parsed.part(part, &params).as_str()),
+            answers.get(day, part),
+            "Incorrect solution for day {}{}",
+            day,
+            part
+        );
+    }
 
     Ok(())
 }
 
 macro_rules! test_day {
-    ($name: ident, $sol_a: literal, $sol_b: literal) => {
+    ($($name: ident),* $(,)?) => {
+        $(
+            // Personal inputs are not checked in; the examples are tested in the library
+            #[test]
+            #[ignore = "needs the personal input in inputs/"]
+            fn $name() -> Result<(), std::io::Error> {
+                let day_name = stringify!($name);
+                let day_num: u8 = day_name[3..].parse().unwrap();
+                test_day(day_num)
+            }
+        )*
+
         #[test]
-        fn $name() -> Result<(), std::io::Error> {
-            let day_name = stringify!($name);
-            let day_num: u8 = day_name[3..].parse().unwrap();
-            test_day(day_num, $sol_a, $sol_b)
+        fn every_answered_day_is_tested() {
+            let answers = Answers::parse(include_str!("../answers.toml")).unwrap();
+            let tested: Vec<u8> = vec![$(stringify!($name)[3..].parse().unwrap()),*];
+            for day in answers.days() {
+                assert!(tested.contains(&day), "Day {day} has answers but is not registered");
+            }
         }
     };
 }
 
-test_day!(day01, "2815556", "23927637");
-test_day!(day02, "371", "426");
-test_day!(day03, "173785482", "83158140");
-test_day!(day04, "2593", "1950");
-test_day!(day05, "6505", "6897");
-test_day!(day06, "5551", "1939");
-test_day!(day07, "3598800864292", "340362529351427");
-test_day!(day08, "344", "1182");
-test_day!(day09, "6154342787400", "6183632723350");
-test_day!(day10, "550", "1255");
-test_day!(day11, "211306", "250783680217283");
-test_day!(day12, "1437300", "849332");
-test_day!(day13, "29201", "104140871044942");
-test_day!(day14, "225521010", "7774");
-test_day!(day15, "1442192", "1448458");
-test_day!(day16, "85396", "428");
-test_day!(day17, "3,6,3,7,0,7,0,3,0", "136904920099226");
-test_day!(day18, "232", "44,64");
-test_day!(day19, "342", "891192814474630");
-test_day!(day20, "1323", "983905");
-test_day!(day21, "184716", "229403562787554");
-test_day!(day22, "15335183969", "1696");
-test_day!(day23, "1337", "aw,fk,gv,hi,hp,ip,jy,kc,lk,og,pj,re,sr");
-test_day!(day24, "56620966442854", "chv,jpj,kgj,rts,vvw,z07,z12,z26");
-test_day!(day25, "3155", "");
+adventofcode_2024::with_days!(test_day);